@@ -1,10 +1,16 @@
+use std::path::Path;
+
 use crate::{
-    crud_query::methods::{
-        generate_query_criteria, generate_sortable_field_enum, new_repo_interface_name,
-        print_single_file,
-    },
-    utils::get_class_name::{
-        extract_entity_from_base_crud_repo_class, get_class_name_and_line_number,
+    crud_query::{
+        filters::{apply_criteria, check_filter_modes},
+        methods::{
+            generate_query_criteria, generate_sortable_field_enum, new_repo_interface_name,
+            print_single_file,
+        },
+        patch::patch_project_files,
+        project_config::CrudProjectConfig,
+        project_files::{crud_files, print_summary, write_project_files},
+        sorting::{check_sort, SortOptions},
     },
     FieldWithType,
 };
@@ -13,93 +19,88 @@ pub fn run(
     entity_name: String,
     entity_id_name: Option<String>,
     sortable_fields: Option<Vec<FieldWithType>>,
-) -> String {
+    force: bool,
+    config: &CrudProjectConfig,
+    sort: &SortOptions,
+) -> Result<String, String> {
     let entity_id_name = entity_id_name.unwrap_or("<REPLACE_WITH_ENTITY_ID_NAME>".to_string());
 
     let base_project_route =
-        std::env::var("BASE_PROJECT_ROUTE").unwrap_or("{No value found}".to_string());
+        std::env::var("BASE_PROJECT_ROUTE").ok().filter(|route| !route.is_empty());
 
     let base_namespace = std::env::var("BASE_NAMESPACE").ok();
 
     println!("ClassName {}", entity_name);
+    check_filter_modes(sortable_fields.as_deref().unwrap_or_default(), &config.filters)?;
+    check_sort(&entity_id_name, sortable_fields.as_deref(), sort)?;
     let sortable_enum = generate_sortable_field_enum(
         entity_name.clone(),
         entity_id_name.clone(),
         base_namespace.clone(),
         sortable_fields.clone(),
+        config,
+        sort,
     );
     let query_criteria_class = generate_query_criteria(
         entity_name.clone(),
         entity_id_name.clone(),
         base_namespace.clone(),
         sortable_fields.clone(),
+        config,
+        sort,
     );
     let new_repo = new_repository(
         entity_name.clone(),
         sortable_fields.clone(),
         base_namespace.clone(),
+        config,
     );
-    let new_interface = new_repo_interface_name(entity_name.clone());
-    return print_single_file(
-        base_project_route,
+    let new_interface = new_repo_interface_name(entity_name.clone(), base_namespace, config);
+    if let Some(route) = &base_project_route {
+        let mut files = crud_files(
+            &entity_name,
+            sortable_enum.clone(),
+            query_criteria_class.clone(),
+            new_repo.clone(),
+            new_interface.clone(),
+            config,
+        );
+        patch_project_files(Path::new(route), &mut files, &entity_name, sortable_fields.as_deref(), config)?;
+        print_summary(&write_project_files(Path::new(route), &files, force)?, force);
+    }
+    Ok(print_single_file(
+        base_project_route.unwrap_or("{No value found}".to_string()),
         entity_name,
         sortable_enum,
         query_criteria_class,
         new_repo,
         new_interface,
-    );
+        config,
+    ))
 }
 
 fn new_repository(
     entity_name: String,
     sortable_fields: Option<Vec<FieldWithType>>,
     namespace: Option<String>,
+    config: &CrudProjectConfig,
 ) -> String {
-    let namespace = match namespace {
-        Some(base_namespace) => format!("namespace {base_namespace}.Infrastructure.Repositories;"),
-        None => "".to_string(),
-    };
+    let namespace = config.repository.namespace_declaration(namespace.as_deref());
+    let base = config.repository.base_clause(&entity_name);
+    let db_context = &config.db_context;
 
     let query_override = match sortable_fields {
-        Some(fields) => {
-            let query:String = fields.clone().into_iter().map(|field| {
-                let field_type = field.field_type.as_str();
-                let field = field.field;
-                match field_type {
-                    x if x.contains("List<") => {
-                        return format!("\n\t\t.WhereIf(criteria.{field}.Any, e => e.{field} == criteria.{field})") 
-                    }
-                    "string" => {
-                         return format!("\n\t\t.WhereIf(!string.IsNullOrEmpty(criteria.{field}), e => e.{field} == criteria.{field})")
-                    },
-                    "int" => {
-                        return format!("\n\t\t.WhereIf(criteria.{field}.HasValue, e => e.{field} == criteria.{field})") 
-                    }
-                    _ => {  return format!("\n // Check this value \n .WhereIf(criteria.{field}, e => e.{field} == criteria.{field})")},
-                }
-              
-            }).collect();
-    
-format!(
-r#"
-    protected override IQueryable<{entity_name}> ApplyCriteria(IQueryable<{entity_name}> query, {entity_name}QueryCriteria criteria)
-    {{
-        //TODO check these for correctness.
-        return query{query};
-    }}
-"#
-)
-        },
-        None => {"".to_string()},
+        Some(fields) => apply_criteria(&entity_name, &fields, &config.filters),
+        None => "".to_string(),
     };
 
 format!(
         r#"
 {namespace}
 
-internal class {entity_name}Repository : BaseQueryCrudRepository<{entity_name}, {entity_name}QueryCriteria, {entity_name}SortableField>, I{entity_name}Repository
+internal class {entity_name}Repository{base}
 {{
-    public {entity_name}Repository(CgwContext context) : base(context)
+    public {entity_name}Repository({db_context} context) : base(context)
     {{
     }}
 {query_override}
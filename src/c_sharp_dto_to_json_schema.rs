@@ -0,0 +1,841 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    c_sharp_dto_to_ts_interface::{
+        collect_declarations, Declaration, EnumSerialization, NullableContext,
+    },
+    utils::{
+        csharp_parser::parse_csharp,
+        csharp_syntax::{Attribute, CsFile, TypeDecl, TypeKind, TypeRef},
+        csharp_types::{can_be_null, COLLECTION_TYPES, DICTIONARY_TYPES, INTEGER_TYPES},
+        enum_values::enum_values,
+        json_naming::{enum_member_name, json_ignore, serialized_name, JsonIgnore, NamingPolicy},
+        type_scopes::TypeScopes,
+        xml_doc::XmlDoc,
+    },
+};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Sets that reject duplicate items.
+const SET_TYPES: [&str; 5] = [
+    "HashSet",
+    "SortedSet",
+    "ISet",
+    "IReadOnlySet",
+    "ImmutableHashSet",
+];
+
+/// The serializer settings of the API, shared with the TypeScript converter.
+#[derive(Clone, Debug, Default)]
+pub struct JsonSchemaOptions {
+    pub enum_serialization: EnumSerialization,
+    pub naming_policy: NamingPolicy,
+    pub nullable_context: NullableContext,
+}
+
+/// Generates a draft 2020-12 JSON Schema for the DTOs in `content`. The first type is
+/// the document's root; every class, struct, record and enum of the input, and every type
+/// they use from `references`, is a definition under `$defs`.
+pub fn csharp_dto_to_json_schema(
+    content: String,
+    references: &[CsFile],
+    options: &JsonSchemaOptions,
+) -> Result<String, String> {
+    let file = parse_csharp(&content)?;
+    let schema = generate_schema(&file, references, options)?;
+    Ok(serde_json::to_string_pretty(&schema).expect("a JSON value always serializes") + "\n")
+}
+
+pub fn generate_schema(
+    file: &CsFile,
+    references: &[CsFile],
+    options: &JsonSchemaOptions,
+) -> Result<Value, String> {
+    let mut roots = Vec::new();
+    for decl in &file.types {
+        collect_declarations(decl, "", &mut roots);
+    }
+    let Some(root) = roots.first() else {
+        return Err("No class, record or enum found in input file".to_string());
+    };
+
+    let mut generator = SchemaGenerator {
+        options,
+        declarations: TypeScopes::default(),
+        defs: RefCell::new(Map::new()),
+        pending: RefCell::new(VecDeque::new()),
+        scope: RefCell::new(String::new()),
+    };
+    let mut known = roots.clone();
+    for reference in references {
+        for decl in &reference.types {
+            collect_declarations(decl, "", &mut known);
+        }
+    }
+    // the first declaration of a path wins, so the input shadows references
+    for declaration in known {
+        generator
+            .declarations
+            .insert(declaration.path.clone(), declaration);
+    }
+
+    for declaration in &roots {
+        generator.pending.borrow_mut().push_back(Definition {
+            name: declaration.name.clone(),
+            path: declaration.path.clone(),
+            decl: declaration.decl,
+            args: Vec::new(),
+        });
+    }
+    generator.generate_defs()?;
+
+    Ok(json!({
+        "$schema": DIALECT,
+        "$ref": definition_path(&root.name),
+        "$defs": generator.defs.into_inner(),
+    }))
+}
+
+/// A `$defs` entry still to be generated: a declaration, with generic arguments
+/// when it is an instantiation such as `PagedResult<CustomerDto>`.
+struct Definition<'a> {
+    name: String,
+    /// Path of the declaration, where the type names it uses are looked up.
+    path: String,
+    decl: &'a TypeDecl,
+    args: Vec<TypeRef>,
+}
+
+struct SchemaGenerator<'a> {
+    options: &'a JsonSchemaOptions,
+    /// C# type path to its declaration and (possibly flattened) definition name.
+    declarations: TypeScopes<Declaration<'a>>,
+    defs: RefCell<Map<String, Value>>,
+    /// Definitions referenced but not generated yet.
+    pending: RefCell<VecDeque<Definition<'a>>>,
+    /// Path of the declaration being generated.
+    scope: RefCell<String>,
+}
+
+impl<'a> SchemaGenerator<'a> {
+    /// Generates every pending definition, and the ones they refer to in turn.
+    fn generate_defs(&self) -> Result<(), String> {
+        loop {
+            let Some(definition) = self.pending.borrow_mut().pop_front() else {
+                return Ok(());
+            };
+            if self.defs.borrow().contains_key(&definition.name) {
+                continue;
+            }
+            // claim the name first so self-references don't queue it again
+            self.defs
+                .borrow_mut()
+                .insert(definition.name.clone(), Value::Null);
+            *self.scope.borrow_mut() = definition.path;
+            let schema = self.declaration_schema(definition.decl, &definition.args)?;
+            self.defs.borrow_mut().insert(definition.name, schema);
+        }
+    }
+
+    /// Generic parameters without an argument accept anything.
+    fn declaration_schema(&self, decl: &TypeDecl, args: &[TypeRef]) -> Result<Value, String> {
+        let any = TypeRef::Named {
+            name: "object".to_string(),
+            args: Vec::new(),
+        };
+        let type_args: HashMap<&str, TypeRef> = decl
+            .type_params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| (param.as_str(), args.get(i).unwrap_or(&any).clone()))
+            .collect();
+        match decl.kind {
+            TypeKind::Enum => self.enum_schema(decl),
+            _ => Ok(self.object_schema(decl, &type_args)),
+        }
+    }
+
+    /// An object with the public instance properties as the serializer writes them.
+    /// Properties that can't be null, and `required` or `[JsonRequired]` ones, are
+    /// required. A base class that is a known DTO is included through `allOf`.
+    fn object_schema(&self, decl: &TypeDecl, type_args: &HashMap<&str, TypeRef>) -> Value {
+        let type_doc = XmlDoc::parse(&decl.doc);
+        let mut schema = annotations(&type_doc, &decl.attributes);
+        let base = decl
+            .base_types
+            .first()
+            .filter(|base| !self.is_enum(base))
+            .and_then(|base| self.reference(base, type_args));
+        if let Some(base) = base {
+            schema.insert("allOf".to_string(), json!([base]));
+        }
+        schema.insert("type".to_string(), json!("object"));
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for property in decl
+            .all_properties()
+            .iter()
+            .filter(|p| p.is_public() && !p.is_static())
+        {
+            let ignore = json_ignore(&property.attributes);
+            if ignore == JsonIgnore::Always {
+                continue;
+            }
+            let name = serialized_name(
+                &property.name,
+                &property.attributes,
+                self.options.naming_policy,
+            );
+            let is_nullable = can_be_null(
+                property,
+                &decl.type_params,
+                self.options.nullable_context == NullableContext::Enable,
+                |ty| self.is_value_type(ty),
+            );
+
+            let mut property_schema = self.schema_of(property.ty.strip_nullable().0, type_args);
+            constrain(&mut property_schema, &property.attributes);
+            if is_nullable {
+                property_schema = or_null(property_schema);
+            }
+            let mut property_doc = XmlDoc::parse(&property.doc);
+            if let (true, Some(param)) = (property.doc.is_empty(), type_doc.param(&property.name)) {
+                // positional record parameters are documented on the record
+                property_doc.summary = param.to_string();
+            }
+            let mut annotated = annotations(&property_doc, &property.attributes);
+            if let Value::Object(property_schema) = property_schema {
+                annotated.extend(property_schema);
+            }
+
+            let must_be_set = !is_nullable
+                || property.is_required()
+                || property.attributes.iter().any(|a| a.is("JsonRequired"));
+            if must_be_set && ignore != JsonIgnore::WhenWriting {
+                required.push(json!(name));
+            }
+            properties.insert(name, Value::Object(annotated));
+        }
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        Value::Object(schema)
+    }
+
+    /// Numbers or names depending on the enum serialization. `[Flags]` enums can hold
+    /// combinations of members, so they aren't restricted to the declared values.
+    /// Fails when a member's value is needed and can't be evaluated.
+    fn enum_schema(&self, decl: &TypeDecl) -> Result<Value, String> {
+        let mut schema = annotations(&XmlDoc::parse(&decl.doc), &decl.attributes);
+        let is_flags = decl.attributes.iter().any(|a| a.is("Flags"));
+        let (json_type, values): (&str, Vec<Value>) = match self.options.enum_serialization {
+            EnumSerialization::Number if is_flags => ("integer", Vec::new()),
+            EnumSerialization::Number => (
+                "integer",
+                decl.enum_members
+                    .iter()
+                    .zip(enum_values(decl))
+                    .map(|(member, value)| {
+                        value.map(Value::from).ok_or(format!(
+                            "can't evaluate the value of {}.{}; give it an explicit value",
+                            decl.name, member.name
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            EnumSerialization::String => (
+                "string",
+                decl.enum_members
+                    .iter()
+                    .map(|member| json!(enum_member_name(member)))
+                    .collect(),
+            ),
+        };
+        schema.insert("type".to_string(), json!(json_type));
+        if !is_flags && !values.is_empty() {
+            schema.insert("enum".to_string(), Value::Array(values));
+        }
+        Ok(Value::Object(schema))
+    }
+
+    /// The schema of a C# type, recursing through arrays, tuples and generic arguments.
+    /// `type_args` are the generic arguments of the declaration being generated.
+    fn schema_of(&self, csharp_type: &TypeRef, type_args: &HashMap<&str, TypeRef>) -> Value {
+        let schema_of = |ty: &TypeRef| self.schema_of(ty, type_args);
+
+        let args = match csharp_type {
+            TypeRef::Nullable(inner) => return or_null(schema_of(inner)),
+            // System.Text.Json writes byte arrays as base64 strings
+            TypeRef::Array(item) if item.simple_name() == Some("byte") => {
+                return json!({ "type": "string", "contentEncoding": "base64" })
+            }
+            TypeRef::Array(item) => return json!({ "type": "array", "items": schema_of(item) }),
+            TypeRef::Tuple(items) => return tuple(items.iter().map(schema_of).collect()),
+            TypeRef::Named { args, .. } => args,
+        };
+        let simple_name = csharp_type.simple_name().unwrap_or_default();
+
+        match (simple_name, args.as_slice()) {
+            // the argument is already written in terms of the caller's types
+            (name, []) if type_args.contains_key(name) => {
+                self.schema_of(&type_args[name], &HashMap::new())
+            }
+            ("Nullable", [inner]) => or_null(schema_of(inner)),
+            ("Tuple" | "ValueTuple", items) if !items.is_empty() => {
+                tuple(items.iter().map(schema_of).collect())
+            }
+            (collection, [item]) if COLLECTION_TYPES.contains(&collection) => {
+                let mut schema = json!({ "type": "array", "items": schema_of(item) });
+                if SET_TYPES.contains(&collection) {
+                    schema["uniqueItems"] = json!(true);
+                }
+                schema
+            }
+            (dictionary, [_, value]) if DICTIONARY_TYPES.contains(&dictionary) => {
+                json!({ "type": "object", "additionalProperties": schema_of(value) })
+            }
+            (name, args) => match builtin_schema(name).filter(|_| args.is_empty()) {
+                Some(schema) => schema,
+                // unknown types accept anything
+                None => self.reference(csharp_type, type_args).unwrap_or(json!({})),
+            },
+        }
+    }
+
+    /// A `$ref` to a declared class, record or enum, queuing its definition.
+    /// Generic instantiations get a definition of their own: `PagedResultOfCustomerDto`.
+    fn reference(
+        &self,
+        csharp_type: &TypeRef,
+        type_args: &HashMap<&str, TypeRef>,
+    ) -> Option<Value> {
+        let declaration = self.lookup(csharp_type)?;
+        let args: Vec<TypeRef> = csharp_type
+            .generic_args()
+            .iter()
+            .map(|arg| self.qualify(&substitute(arg, type_args)))
+            .collect();
+        let name = match args.is_empty() {
+            true => declaration.name.clone(),
+            false => format!("{}{}", declaration.name, self.args_suffix(&args)),
+        };
+        let schema = json!({ "$ref": definition_path(&name) });
+        self.pending.borrow_mut().push_back(Definition {
+            name,
+            path: declaration.path.clone(),
+            decl: declaration.decl,
+            args,
+        });
+        Some(schema)
+    }
+
+    /// The declared type `csharp_type` names in the declaration being generated.
+    fn lookup(&self, csharp_type: &TypeRef) -> Option<&Declaration<'a>> {
+        match csharp_type {
+            TypeRef::Named { name, .. } => self.declarations.resolve(name, &self.scope.borrow()),
+            _ => None,
+        }
+    }
+
+    /// Names declared types by their path, so that generic arguments mean the same
+    /// inside the generic definition they are passed to.
+    fn qualify(&self, csharp_type: &TypeRef) -> TypeRef {
+        match csharp_type {
+            TypeRef::Named { name, args } => TypeRef::Named {
+                name: self
+                    .lookup(csharp_type)
+                    .map_or(name.clone(), |declaration| declaration.path.clone()),
+                args: args.iter().map(|arg| self.qualify(arg)).collect(),
+            },
+            TypeRef::Array(item) => TypeRef::Array(Box::new(self.qualify(item))),
+            TypeRef::Nullable(inner) => TypeRef::Nullable(Box::new(self.qualify(inner))),
+            TypeRef::Tuple(items) => {
+                TypeRef::Tuple(items.iter().map(|item| self.qualify(item)).collect())
+            }
+        }
+    }
+
+    /// `Of` and the generic arguments' names: `OfCustomerDto`, `OfStringAndInt`.
+    fn args_suffix(&self, args: &[TypeRef]) -> String {
+        let names: Vec<String> = args.iter().map(|arg| self.type_name(arg)).collect();
+        format!("Of{}", names.join("And"))
+    }
+
+    fn type_name(&self, csharp_type: &TypeRef) -> String {
+        match csharp_type {
+            TypeRef::Named { args, .. } => {
+                let name = match self.lookup(csharp_type) {
+                    Some(declaration) => declaration.name.clone(),
+                    None => uppercase_first(csharp_type.simple_name().unwrap_or_default()),
+                };
+                match args.is_empty() {
+                    true => name,
+                    false => name + &self.args_suffix(args),
+                }
+            }
+            TypeRef::Array(item) => format!("{}Array", self.type_name(item)),
+            TypeRef::Nullable(inner) => format!("Nullable{}", self.type_name(inner)),
+            TypeRef::Tuple(items) => format!("Tuple{}", self.args_suffix(items)),
+        }
+    }
+
+    fn is_enum(&self, csharp_type: &TypeRef) -> bool {
+        self.lookup(csharp_type)
+            .is_some_and(|declaration| declaration.decl.kind == TypeKind::Enum)
+    }
+
+    /// Enums and structs declared in the input or its references.
+    fn is_value_type(&self, csharp_type: &TypeRef) -> bool {
+        self.lookup(csharp_type).is_some_and(|declaration| {
+            matches!(
+                declaration.decl.kind,
+                TypeKind::Enum | TypeKind::Struct | TypeKind::RecordStruct
+            )
+        })
+    }
+}
+
+/// Replaces generic parameters by the arguments they stand for.
+fn substitute(csharp_type: &TypeRef, type_args: &HashMap<&str, TypeRef>) -> TypeRef {
+    match csharp_type {
+        TypeRef::Named { name, args }
+            if args.is_empty() && type_args.contains_key(name.as_str()) =>
+        {
+            type_args[name.as_str()].clone()
+        }
+        TypeRef::Named { name, args } => TypeRef::Named {
+            name: name.clone(),
+            args: args.iter().map(|arg| substitute(arg, type_args)).collect(),
+        },
+        TypeRef::Array(item) => TypeRef::Array(Box::new(substitute(item, type_args))),
+        TypeRef::Nullable(inner) => TypeRef::Nullable(Box::new(substitute(inner, type_args))),
+        TypeRef::Tuple(items) => TypeRef::Tuple(
+            items
+                .iter()
+                .map(|item| substitute(item, type_args))
+                .collect(),
+        ),
+    }
+}
+
+/// Schemas of the types System.Text.Json writes as JSON primitives.
+fn builtin_schema(name: &str) -> Option<Value> {
+    let schema = match name {
+        integer if INTEGER_TYPES.contains(&integer) => json!({ "type": "integer" }),
+        "float" | "Single" | "double" | "Double" | "decimal" | "Decimal" | "Half" => {
+            json!({ "type": "number" })
+        }
+        "string" | "String" | "char" | "Char" | "TimeSpan" => json!({ "type": "string" }),
+        "bool" | "Boolean" => json!({ "type": "boolean" }),
+        "Guid" => json!({ "type": "string", "format": "uuid" }),
+        "DateTime" | "DateTimeOffset" => json!({ "type": "string", "format": "date-time" }),
+        "DateOnly" => json!({ "type": "string", "format": "date" }),
+        "TimeOnly" => json!({ "type": "string", "format": "time" }),
+        "Uri" => json!({ "type": "string", "format": "uri" }),
+        "JsonObject" => json!({ "type": "object" }),
+        "JsonArray" => json!({ "type": "array" }),
+        "object" | "Object" | "dynamic" | "JsonElement" | "JsonNode" | "JsonDocument" => json!({}),
+        _ => return None,
+    };
+    Some(schema)
+}
+
+/// Adds DataAnnotations constraints that apply to the kind of value `schema` describes:
+/// lengths to strings and arrays, `[Range]` to numbers and formats to strings.
+fn constrain(schema: &mut Value, attributes: &[Attribute]) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+    let json_type = schema
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let is_string = json_type == "string" && !schema.contains_key("contentEncoding");
+    let is_array = json_type == "array";
+    let is_number = matches!(json_type, "integer" | "number");
+    let (min_length, max_length) = match is_array {
+        true => ("minItems", "maxItems"),
+        false => ("minLength", "maxLength"),
+    };
+    // `[Range(typeof(decimal), "0", "1")]` and constants such as `int.MaxValue` aren't numeric
+    let number = |value: &str| {
+        serde_json::from_str::<Value>(value.trim())
+            .ok()
+            .filter(Value::is_number)
+    };
+    let positional = |attribute: &Attribute, index: usize| {
+        let arg = attribute
+            .args
+            .iter()
+            .filter(|a| a.name.is_none())
+            .nth(index)?;
+        number(&arg.value)
+    };
+
+    let mut constraints: Vec<(&str, Value)> = Vec::new();
+    for attribute in attributes {
+        if is_string && attribute.is("EmailAddress") {
+            constraints.push(("format", json!("email")));
+        } else if is_string && attribute.is("Url") {
+            constraints.push(("format", json!("uri")));
+        } else if is_string && attribute.is("RegularExpression") {
+            if let Some(pattern) = attribute.string_arg() {
+                constraints.push(("pattern", json!(pattern)));
+            }
+        } else if (is_string || is_array) && attribute.is("MinLength") {
+            constraints.extend(positional(attribute, 0).map(|min| (min_length, min)));
+        } else if (is_string || is_array) && attribute.is("MaxLength") {
+            constraints.extend(positional(attribute, 0).map(|max| (max_length, max)));
+        } else if is_string && attribute.is("StringLength") {
+            let min = attribute
+                .args
+                .iter()
+                .find(|a| a.name.as_deref() == Some("MinimumLength"))
+                .and_then(|a| number(&a.value));
+            constraints.extend(min.map(|min| ("minLength", min)));
+            constraints.extend(positional(attribute, 0).map(|max| ("maxLength", max)));
+        } else if is_number && attribute.is("Range") {
+            constraints.extend(positional(attribute, 0).map(|min| ("minimum", min)));
+            constraints.extend(positional(attribute, 1).map(|max| ("maximum", max)));
+        }
+    }
+    for (keyword, value) in constraints {
+        schema.insert(keyword.to_string(), value);
+    }
+}
+
+/// `description` from an XML doc comment, and `deprecated` for `[Obsolete]`.
+fn annotations(doc: &XmlDoc, attributes: &[Attribute]) -> Map<String, Value> {
+    let mut annotations = Map::new();
+    let description: Vec<&str> = [doc.summary.as_str(), doc.remarks.as_str()]
+        .into_iter()
+        .filter(|text| !text.is_empty())
+        .collect();
+    if !description.is_empty() {
+        annotations.insert("description".to_string(), json!(description.join("\n\n")));
+    }
+    if attributes.iter().any(|a| a.is("Obsolete")) {
+        annotations.insert("deprecated".to_string(), json!(true));
+    }
+    annotations
+}
+
+/// Also accepts `null`: added to a single `type`, or as an `anyOf` alternative.
+fn or_null(schema: Value) -> Value {
+    match schema.get("type").and_then(Value::as_str) {
+        Some(json_type) => {
+            let mut schema = schema.clone();
+            schema["type"] = json!([json_type, "null"]);
+            schema
+        }
+        // `{}` already accepts null
+        None if schema.as_object().is_some_and(Map::is_empty) => schema,
+        None => json!({ "anyOf": [schema, { "type": "null" }] }),
+    }
+}
+
+fn tuple(items: Vec<Value>) -> Value {
+    json!({ "type": "array", "prefixItems": items, "items": false, "minItems": items.len() })
+}
+
+fn definition_path(name: &str) -> String {
+    format!("#/$defs/{name}")
+}
+
+fn uppercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(dto: &str, options: &JsonSchemaOptions) -> Value {
+        generate_schema(&parse_csharp(dto).unwrap(), &[], options).unwrap()
+    }
+
+    #[test]
+    fn test_document_with_refs_enums_and_docs() {
+        let schema = schema(
+            r#"
+            /// <summary>A customer.</summary>
+            /// <remarks>Read only.</remarks>
+            public class CustomerDto : EntityDto
+            {
+                public string Name { get; set; }
+                public string? Email { get; set; }
+                public int? Age { get; set; }
+                public Status Status { get; set; }
+                public AddressDto? Address { get; set; }
+                public List<AddressDto> Addresses { get; set; } = new();
+                public HashSet<Guid> Tags { get; set; } = new();
+                public Dictionary<string, decimal> Balances { get; set; } = new();
+                public byte[] Photo { get; set; } = [];
+                public (int, string) Pair { get; set; }
+                /// <summary>Use Email.</summary>
+                [Obsolete] public string? Mail { get; set; }
+                [JsonIgnore] public string Secret { get; set; }
+            }
+            public class EntityDto { public Guid Id { get; set; } public DateTime CreatedAt { get; set; } }
+            public enum Status { Active = 1, Blocked }
+            public record AddressDto(string Street);
+            "#,
+            &JsonSchemaOptions::default(),
+        );
+        let expected = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$ref": "#/$defs/CustomerDto",
+            "$defs": {
+                "CustomerDto": {
+                    "description": "A customer.\n\nRead only.",
+                    "allOf": [{ "$ref": "#/$defs/EntityDto" }],
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "email": { "type": ["string", "null"] },
+                        "age": { "type": ["integer", "null"] },
+                        "status": { "$ref": "#/$defs/Status" },
+                        "address": { "anyOf": [{ "$ref": "#/$defs/AddressDto" }, { "type": "null" }] },
+                        "addresses": { "type": "array", "items": { "$ref": "#/$defs/AddressDto" } },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string", "format": "uuid" },
+                            "uniqueItems": true
+                        },
+                        "balances": { "type": "object", "additionalProperties": { "type": "number" } },
+                        "photo": { "type": "string", "contentEncoding": "base64" },
+                        "pair": {
+                            "type": "array",
+                            "prefixItems": [{ "type": "integer" }, { "type": "string" }],
+                            "items": false,
+                            "minItems": 2
+                        },
+                        "mail": {
+                            "description": "Use Email.",
+                            "deprecated": true,
+                            "type": ["string", "null"]
+                        }
+                    },
+                    "required": ["name", "status", "addresses", "tags", "balances", "photo", "pair"]
+                },
+                "EntityDto": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "format": "uuid" },
+                        "createdAt": { "type": "string", "format": "date-time" }
+                    },
+                    "required": ["id", "createdAt"]
+                },
+                "Status": { "type": "integer", "enum": [1, 2] },
+                "AddressDto": {
+                    "type": "object",
+                    "properties": { "street": { "type": "string" } },
+                    "required": ["street"]
+                }
+            }
+        });
+        assert_eq!(schema, expected);
+    }
+
+    #[test]
+    fn test_referenced_files_and_generic_instantiations() {
+        let input = parse_csharp(
+            r#"
+            public class CustomerPageDto
+            {
+                public PagedResult<CustomerDto> Page { get; set; }
+            }
+            public class PagedResult<T>
+            {
+                public List<T> Items { get; set; } = new();
+                public int Total { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let reference = parse_csharp(
+            "public record CustomerDto(string Name, Status? Status); public enum Status { A, B }",
+        )
+        .unwrap();
+        let options = JsonSchemaOptions {
+            enum_serialization: EnumSerialization::String,
+            ..Default::default()
+        };
+        let schema = generate_schema(&input, &[reference], &options).unwrap();
+        let defs = schema["$defs"].as_object().unwrap();
+        assert_eq!(
+            defs.keys().collect::<Vec<_>>(),
+            vec![
+                "CustomerPageDto",
+                "PagedResult",
+                "PagedResultOfCustomerDto",
+                "CustomerDto",
+                "Status"
+            ]
+        );
+        assert_eq!(
+            defs["PagedResult"]["properties"]["items"],
+            json!({ "type": "array", "items": {} })
+        );
+        assert_eq!(
+            defs["PagedResultOfCustomerDto"]["properties"]["items"],
+            json!({ "type": "array", "items": { "$ref": "#/$defs/CustomerDto" } })
+        );
+        assert_eq!(
+            defs["CustomerDto"]["properties"]["status"],
+            json!({ "anyOf": [{ "$ref": "#/$defs/Status" }, { "type": "null" }] })
+        );
+        assert_eq!(
+            defs["Status"],
+            json!({ "type": "string", "enum": ["A", "B"] })
+        );
+    }
+
+    #[test]
+    fn test_same_named_nested_types_resolve_in_their_parent() {
+        let schema = schema(
+            r#"
+            public class CreateOrderRequest
+            {
+                public Item Item { get; set; }
+                public class Item { public int ProductId { get; set; } }
+            }
+            public class CreateOrderResponse
+            {
+                public PagedResult<Item> Items { get; set; }
+                public class Item { public decimal Price { get; set; } }
+            }
+            public class PagedResult<T> { public List<T> Items { get; set; } = new(); }
+            "#,
+            &JsonSchemaOptions::default(),
+        );
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["CreateOrderRequest"]["properties"]["item"],
+            json!({ "$ref": "#/$defs/CreateOrderRequestItem" })
+        );
+        assert_eq!(
+            defs["CreateOrderResponse"]["properties"]["items"],
+            json!({ "$ref": "#/$defs/PagedResultOfCreateOrderResponseItem" })
+        );
+        assert_eq!(
+            defs["PagedResultOfCreateOrderResponseItem"]["properties"]["items"]["items"],
+            json!({ "$ref": "#/$defs/CreateOrderResponseItem" })
+        );
+        assert_eq!(
+            defs["CreateOrderResponseItem"]["properties"]["price"],
+            json!({ "type": "number" })
+        );
+    }
+
+    #[test]
+    fn test_nullability_and_required_members() {
+        let schema = schema(
+            r#"
+            public class Dto
+            {
+                public required string? Note { get; set; }
+                [JsonRequired] public int? Count { get; set; }
+                [Required] public string? Code { get; set; }
+                [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingDefault)] public int Version { get; set; }
+                public string Name { get; set; }
+            }
+            "#,
+            &JsonSchemaOptions {
+                nullable_context: NullableContext::Disable,
+                ..Default::default()
+            },
+        );
+        let dto = &schema["$defs"]["Dto"];
+        assert_eq!(
+            dto["properties"]["note"],
+            json!({ "type": ["string", "null"] })
+        );
+        assert_eq!(dto["properties"]["code"], json!({ "type": "string" }));
+        assert_eq!(
+            dto["properties"]["name"],
+            json!({ "type": ["string", "null"] })
+        );
+        assert_eq!(dto["required"], json!(["note", "count", "code"]));
+    }
+
+    #[test]
+    fn test_data_annotations_and_flags() {
+        let schema = schema(
+            r#"
+            public class Dto
+            {
+                [StringLength(50, MinimumLength = 2)] public string Name { get; set; }
+                [EmailAddress, MaxLength(100)] public string Email { get; set; }
+                [RegularExpression(@"^\d+$")] public string? Digits { get; set; }
+                [Range(1, 10)] public decimal Rating { get; set; }
+                [Range(0, int.MaxValue)] public int Count { get; set; }
+                [MinLength(1)] public List<string> Tags { get; set; } = new();
+                public Permissions Permissions { get; set; }
+            }
+            [Flags] public enum Permissions { Read = 1, Write = 2 }
+            "#,
+            &JsonSchemaOptions::default(),
+        );
+        let properties = &schema["$defs"]["Dto"]["properties"];
+        assert_eq!(
+            properties["name"],
+            json!({ "type": "string", "minLength": 2, "maxLength": 50 })
+        );
+        assert_eq!(
+            properties["email"],
+            json!({ "type": "string", "format": "email", "maxLength": 100 })
+        );
+        assert_eq!(
+            properties["digits"],
+            json!({ "type": ["string", "null"], "pattern": "^\\d+$" })
+        );
+        assert_eq!(
+            properties["rating"],
+            json!({ "type": "number", "minimum": 1, "maximum": 10 })
+        );
+        assert_eq!(
+            properties["count"],
+            json!({ "type": "integer", "minimum": 0 })
+        );
+        assert_eq!(properties["tags"]["minItems"], json!(1));
+        assert_eq!(schema["$defs"]["Permissions"], json!({ "type": "integer" }));
+    }
+
+    #[test]
+    fn test_enum_value_that_cannot_be_evaluated_is_an_error() {
+        let source = parse_csharp("public enum Level { Low = Limits.Min, High }").unwrap();
+        let error = generate_schema(&source, &[], &JsonSchemaOptions::default()).unwrap_err();
+        assert!(error.contains("Level.Low"), "{error}");
+        let options = JsonSchemaOptions {
+            enum_serialization: EnumSerialization::String,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_schema(&source, &[], &options).unwrap()["$defs"]["Level"]["enum"],
+            json!(["Low", "High"])
+        );
+    }
+
+    #[test]
+    fn test_input_without_types_is_an_error() {
+        assert!(csharp_dto_to_json_schema(
+            "public interface IDto { }".to_string(),
+            &[],
+            &JsonSchemaOptions::default()
+        )
+        .is_err());
+    }
+}
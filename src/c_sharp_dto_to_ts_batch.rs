@@ -0,0 +1,439 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    c_sharp_dto_to_ts_interface::{collect_declarations, convert_file, is_emitted, TsOptions},
+    utils::{csharp_parser::parse_csharp, csharp_syntax::CsFile},
+};
+
+/// How the generated TypeScript of a batch is split into files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum TsLayout {
+    /// One `.ts` per top-level type, named after it; nested types stay with their parent
+    #[default]
+    PerType,
+    /// One `.ts` per `.cs` file, named after it
+    PerFile,
+}
+
+/// A directory or a glob pattern is converted as a batch; anything else is a single file.
+pub fn is_batch_input(input: &str) -> bool {
+    Path::new(input).is_dir() || input.contains(['*', '?', '['])
+}
+
+/// Converts every `.cs` file under a directory, or every file matching a glob, into
+/// `out_dir`, mirroring the folders below the input root. Returns the files written.
+pub fn run(
+    input: &str,
+    out_dir: &Path,
+    layout: TsLayout,
+    options: &TsOptions,
+) -> Result<Vec<PathBuf>, String> {
+    let mut sources = Vec::new();
+    for (path, relative_path) in find_sources(input)? {
+        let content = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        sources.push((relative_path, content));
+    }
+    if sources.is_empty() {
+        return Err(format!("no C# files found for {input}"));
+    }
+
+    let mut written = Vec::new();
+    for (relative_path, content) in convert_sources(&sources, layout, options)? {
+        let path = out_dir.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+        }
+        fs::write(&path, content).map_err(|e| format!("{}: {e}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Converts parsed sources, given by their path relative to the input root, into
+/// TypeScript files and an `index.ts` barrel per folder. Types used across files
+/// are imported by relative path.
+pub fn convert_sources(
+    sources: &[(PathBuf, String)],
+    layout: TsLayout,
+    options: &TsOptions,
+) -> Result<Vec<(PathBuf, String)>, String> {
+    // output path without extension, and the declarations that go there
+    let mut modules: Vec<(PathBuf, CsFile)> = Vec::new();
+    // the source each module comes from, to name both sides of a collision
+    let mut module_sources: Vec<&Path> = Vec::new();
+    for (relative_path, content) in sources {
+        let file =
+            parse_csharp(content).map_err(|e| format!("{}: {e}", relative_path.display()))?;
+        let folder = relative_path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        match layout {
+            TsLayout::PerFile => {
+                if file.all_types().iter().any(|decl| is_emitted(decl.kind)) {
+                    let stem = relative_path.file_stem().unwrap_or_default();
+                    modules.push((folder.join(stem), file));
+                    module_sources.push(relative_path);
+                }
+            }
+            TsLayout::PerType => {
+                for decl in file.types.iter().filter(|decl| is_emitted(decl.kind)) {
+                    let single = CsFile {
+                        usings: file.usings.clone(),
+                        namespace: file.namespace.clone(),
+                        types: vec![decl.clone()],
+                    };
+                    modules.push((folder.join(&decl.name), single));
+                    module_sources.push(relative_path);
+                }
+            }
+        }
+    }
+
+    check_collisions(&modules, &module_sources)?;
+
+    let mut outputs = Vec::new();
+    for (path, file) in &modules {
+        let folder = path.parent().unwrap_or(Path::new(""));
+        let references: Vec<(String, &CsFile)> = modules
+            .iter()
+            .filter(|(other, _)| other != path)
+            .map(|(other, other_file)| (relative_import(folder, other), other_file))
+            .collect();
+        let content = convert_file(file, &references, options)
+            .map_err(|e| format!("{}: {e}", ts_file(path).display()))?;
+        outputs.push((ts_file(path), content));
+    }
+
+    let module_paths: Vec<&PathBuf> = modules.iter().map(|(path, _)| path).collect();
+    outputs.extend(barrels(&module_paths));
+    Ok(outputs)
+}
+
+/// Fails when two modules would be written to the same file, a barrel would re-export
+/// a type from two modules, or a module and a folder would both be imported as the
+/// same specifier, e.g. `Shared.ts` and `Shared/index.ts`. Every barrel up to the root
+/// re-exports a module's types, nested ones under their flattened names.
+fn check_collisions(modules: &[(PathBuf, CsFile)], sources: &[&Path]) -> Result<(), String> {
+    let mut seen: BTreeMap<&Path, &Path> = BTreeMap::new();
+    let mut exported: BTreeMap<(&Path, String), &Path> = BTreeMap::new();
+    for ((path, file), source) in modules.iter().zip(sources) {
+        if let Some(other) = seen.insert(path, source) {
+            return Err(format!(
+                "{} and {} both generate {}",
+                other.display(),
+                source.display(),
+                ts_file(path).display()
+            ));
+        }
+        let mut declarations = Vec::new();
+        for decl in &file.types {
+            collect_declarations(decl, "", &mut declarations);
+        }
+        for declaration in &declarations {
+            for folder in path.ancestors().skip(1) {
+                let key = (folder, declaration.name.clone());
+                if let Some(other) = exported.insert(key, source) {
+                    return Err(format!(
+                        "{} and {} both declare {}, which {} would export twice",
+                        other.display(),
+                        source.display(),
+                        declaration.name,
+                        folder.join("index.ts").display()
+                    ));
+                }
+            }
+        }
+    }
+    for (path, source) in &seen {
+        let folder = seen
+            .iter()
+            .find(|(other, _)| other.parent().is_some_and(|p| p.starts_with(path)));
+        if let Some((other, other_source)) = folder {
+            return Err(format!(
+                "{} generates {} while {} generates {}; both would be imported as ./{}",
+                source.display(),
+                ts_file(path).display(),
+                other_source.display(),
+                ts_file(other).display(),
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `index.ts` files re-exporting every module in a folder and every subfolder's barrel.
+fn barrels(module_paths: &[&PathBuf]) -> Vec<(PathBuf, String)> {
+    let mut exports: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+    for path in module_paths {
+        let mut child = path.as_path();
+        while let Some(folder) = child.parent() {
+            let name = child.file_name().unwrap_or_default().to_string_lossy();
+            exports
+                .entry(folder.to_path_buf())
+                .or_default()
+                .insert(format!("./{name}"));
+            child = folder;
+        }
+    }
+    exports
+        .into_iter()
+        .map(|(folder, names)| {
+            let lines: String = names
+                .iter()
+                .map(|name| format!("export * from '{name}';\n"))
+                .collect();
+            (folder.join("index.ts"), lines)
+        })
+        .collect()
+}
+
+fn ts_file(module_path: &Path) -> PathBuf {
+    let name = module_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    module_path.with_file_name(format!("{name}.ts"))
+}
+
+/// Module specifier of `module` as imported from a file in `from_folder`: `./Address`,
+/// `../Shared/Money`. Both paths are relative to the output root.
+fn relative_import(from_folder: &Path, module: &Path) -> String {
+    let from: Vec<Component> = from_folder.components().collect();
+    let to: Vec<Component> = module.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().to_string()),
+    );
+    parts.join("/")
+}
+
+/// Every `.cs` file under a directory, or every file matching a glob pattern, with
+/// its path relative to the directory or to the pattern's fixed leading folders.
+fn find_sources(input: &str) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let (root, mut paths) = match Path::new(input).is_dir() {
+        true => {
+            let mut paths = Vec::new();
+            collect_cs_files(Path::new(input), &mut paths)?;
+            (PathBuf::from(input), paths)
+        }
+        false => {
+            let paths = glob::glob(input)
+                .map_err(|e| format!("invalid pattern {input}: {e}"))?
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect();
+            (glob_root(input), paths)
+        }
+    };
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let relative_path = path.strip_prefix(&root).unwrap_or(&path).to_path_buf();
+            (path, relative_path)
+        })
+        .collect())
+}
+
+fn collect_cs_files(folder: &Path, paths: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(folder).map_err(|e| format!("{}: {e}", folder.display()))?;
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_cs_files(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "cs") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The leading folders of a glob pattern that contain no wildcards: `Dtos/**/*.cs` → `Dtos`.
+fn glob_root(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources() -> Vec<(PathBuf, String)> {
+        vec![
+            (
+                PathBuf::from("Customers/CustomerDto.cs"),
+                r#"
+                public class CustomerDto
+                {
+                    public AddressDto Address { get; set; }
+                    public Money Balance { get; set; }
+                }
+                public class AddressDto { public string Street { get; set; } }
+                "#
+                .to_string(),
+            ),
+            (
+                PathBuf::from("Shared/Money.cs"),
+                "public record Money(decimal Amount, string Currency);".to_string(),
+            ),
+            (
+                PathBuf::from("Shared/IRepository.cs"),
+                "public interface IRepository { }".to_string(),
+            ),
+        ]
+    }
+
+    fn output<'a>(outputs: &'a [(PathBuf, String)], path: &str) -> &'a str {
+        outputs
+            .iter()
+            .find(|(p, _)| p == Path::new(path))
+            .map(|(_, content)| content.as_str())
+            .unwrap_or_else(|| panic!("{path} was not generated"))
+    }
+
+    #[test]
+    fn test_colliding_outputs_are_an_error() {
+        let duplicate = vec![
+            (
+                PathBuf::from("Shared/A.cs"),
+                "public class Money { }".to_string(),
+            ),
+            (
+                PathBuf::from("Shared/B.cs"),
+                "public class Money { }".to_string(),
+            ),
+        ];
+        let error =
+            convert_sources(&duplicate, TsLayout::PerType, &TsOptions::default()).unwrap_err();
+        assert!(error.contains("Shared/A.cs and Shared/B.cs"), "{error}");
+        let error =
+            convert_sources(&duplicate, TsLayout::PerFile, &TsOptions::default()).unwrap_err();
+        assert!(error.contains("both declare Money"), "{error}");
+
+        let mut shadowed = sources();
+        shadowed.push((
+            PathBuf::from("Shared.cs"),
+            "public class Shared { }".to_string(),
+        ));
+        let error =
+            convert_sources(&shadowed, TsLayout::PerType, &TsOptions::default()).unwrap_err();
+        assert!(error.contains("./Shared"), "{error}");
+
+        let siblings = vec![
+            (
+                PathBuf::from("Orders/OrderDto.cs"),
+                "public class OrderDto { } public class Address { }".to_string(),
+            ),
+            (
+                PathBuf::from("Customers/CustomerDto.cs"),
+                "public class CustomerDto { } public class Address { }".to_string(),
+            ),
+        ];
+        for layout in [TsLayout::PerType, TsLayout::PerFile] {
+            let error = convert_sources(&siblings, layout, &TsOptions::default()).unwrap_err();
+            assert!(
+                error.contains("both declare Address, which index.ts would export twice"),
+                "{error}"
+            );
+        }
+
+        let nested = vec![
+            (
+                PathBuf::from("Orders/Order.cs"),
+                "public class Order { public class Line { } }".to_string(),
+            ),
+            (
+                PathBuf::from("Orders/OrderLine.cs"),
+                "public class OrderLine { }".to_string(),
+            ),
+        ];
+        let error = convert_sources(&nested, TsLayout::PerFile, &TsOptions::default()).unwrap_err();
+        assert!(error.contains("both declare OrderLine"), "{error}");
+    }
+
+    #[test]
+    fn test_one_file_per_type() {
+        let outputs =
+            convert_sources(&sources(), TsLayout::PerType, &TsOptions::default()).unwrap();
+        let paths: Vec<String> = outputs
+            .iter()
+            .map(|(path, _)| path.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "Customers/CustomerDto.ts",
+                "Customers/AddressDto.ts",
+                "Shared/Money.ts",
+                "index.ts",
+                "Customers/index.ts",
+                "Shared/index.ts",
+            ]
+        );
+        assert_eq!(
+            output(&outputs, "Customers/CustomerDto.ts"),
+            "import type { Money } from '../Shared/Money';\n\
+             import type { AddressDto } from './AddressDto';\n\
+             \n\
+             export interface CustomerDto {\n    address: AddressDto;\n    balance: Money;\n}\n"
+        );
+        assert_eq!(
+            output(&outputs, "index.ts"),
+            "export * from './Customers';\nexport * from './Shared';\n"
+        );
+        assert_eq!(
+            output(&outputs, "Customers/index.ts"),
+            "export * from './AddressDto';\nexport * from './CustomerDto';\n"
+        );
+    }
+
+    #[test]
+    fn test_one_file_per_source_file() {
+        let outputs =
+            convert_sources(&sources(), TsLayout::PerFile, &TsOptions::default()).unwrap();
+        let customer = output(&outputs, "Customers/CustomerDto.ts");
+        assert!(customer.starts_with("import type { Money } from '../Shared/Money';\n"));
+        assert!(customer.contains("export interface AddressDto"));
+        assert!(!outputs
+            .iter()
+            .any(|(path, _)| path == Path::new("Shared/IRepository.ts")));
+    }
+
+    #[test]
+    fn test_relative_imports() {
+        assert_eq!(
+            relative_import(Path::new(""), Path::new("Money")),
+            "./Money"
+        );
+        assert_eq!(
+            relative_import(Path::new("A/B"), Path::new("A/C/Money")),
+            "../C/Money"
+        );
+        assert_eq!(
+            relative_import(Path::new("A"), Path::new("A/B/Money")),
+            "./B/Money"
+        );
+    }
+
+    #[test]
+    fn test_glob_root() {
+        assert_eq!(glob_root("Dtos/**/*.cs"), PathBuf::from("Dtos"));
+        assert_eq!(glob_root("src/Dtos/*Dto.cs"), PathBuf::from("src/Dtos"));
+        assert_eq!(glob_root("*.cs"), PathBuf::from(""));
+    }
+}
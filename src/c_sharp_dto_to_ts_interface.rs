@@ -1,105 +1,890 @@
-pub fn csharp_dto_to_ts_interface(content: String) -> String {
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+};
+
+use crate::utils::{
+    csharp_parser::parse_csharp,
+    csharp_syntax::{Attribute, CsFile, EnumMember, Property, TypeDecl, TypeKind, TypeRef},
+    csharp_types::{can_be_null, COLLECTION_TYPES, DICTIONARY_TYPES, INTEGER_TYPES},
+    enum_values::enum_values,
+    json_naming::{enum_member_name, json_ignore, serialized_name, JsonIgnore, NamingPolicy},
+    ts_type_map::TsTypeMap,
+    type_scopes::{type_path, TypeScopes},
+    xml_doc::XmlDoc,
+};
+
+/// What is generated for each DTO.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum TsFormat {
+    /// `export interface CustomerDto { ... }`
+    #[default]
+    Interface,
+    /// `export const CustomerDtoSchema = z.object({ ... })` and its `z.infer` type
+    Zod,
+}
+
+/// How C# enums are written out in TypeScript.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum EnumStyle {
+    /// `export enum Status { Active = 1 }`
+    #[default]
+    Enum,
+    /// `export const Status = { Active: 1 } as const` plus a type of the same name
+    ConstObject,
+    /// `export type Status = 1 | 2`
+    Union,
+}
+
+/// How the API puts enum values on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum EnumSerialization {
+    /// Numeric values, the System.Text.Json default
+    #[default]
+    Number,
+    /// Member names, as written by `JsonStringEnumConverter`
+    String,
+}
+
+/// How properties that can be null are typed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum NullablePolicy {
+    /// `prop?: T`; `required` members, which always carry their key, get `prop: T | null`
+    #[default]
+    Optional,
+    /// `prop: T | null`, as the serializer writes null values by default
+    Null,
+    /// `prop: T`, nullability is not emitted
+    Ignore,
+}
+
+/// Nullable annotation context for files without a `#nullable` directive,
+/// i.e. the project's `<Nullable>` setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum NullableContext {
+    /// Reference types are nullable only when written as `T?`
+    #[default]
+    Enable,
+    /// Every reference type may be null
+    Disable,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TsOptions {
+    pub format: TsFormat,
+    pub enum_style: EnumStyle,
+    pub enum_serialization: EnumSerialization,
+    pub naming_policy: NamingPolicy,
+    pub nullable_policy: NullablePolicy,
+    pub nullable_context: NullableContext,
+    pub type_map: TsTypeMap,
+}
+
+/// Another DTO file, converted separately, whose types the input may refer to.
+pub struct TsModule {
+    /// Module specifier the generated file imports it by, e.g. `./CustomerDto`.
+    pub import_path: String,
+    pub file: CsFile,
+}
+
+impl TsModule {
+    /// Parses a referenced C# file. Its TypeScript output is assumed to sit next to
+    /// ours under the same file stem: `Dtos/CustomerDto.cs` is imported from `./CustomerDto`.
+    pub fn from_file(path: &str, content: &str) -> Result<Self, String> {
+        let stem = Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or(format!("invalid reference file name: {path}"))?;
+        let file = parse_csharp(content).map_err(|e| format!("{path}: {e}"))?;
+        Ok(Self {
+            import_path: format!("./{stem}"),
+            file,
+        })
+    }
+}
+
+pub fn csharp_dto_to_ts_interface(
+    content: String,
+    references: &[TsModule],
+    options: &TsOptions,
+) -> Result<String, String> {
     // Parse the C# DTO and generate the TypeScript interface
-    let ts_interface = convert_to_typescript_interface(&content);
-    ts_interface
-}
-
-fn convert_to_typescript_interface(dto_content: &str) -> String {
-    let binding = dto_content
-        .trim()
-        .replace('\n', "")
-        .replace('\t', "")
-        .replace('\r', "");
-    let binding: Vec<&str> = binding.split(' ').collect();
-
-    let mut arrays: Vec<Vec<&str>> = Vec::new();
-    let mut current_array: Vec<&str> = Vec::new();
-    for line in binding.clone() {
-        if line.trim().is_empty() {
-            if !current_array.is_empty() {
-                arrays.push(current_array.clone());
-                current_array.clear();
-            }
-        } else {
-            current_array.push(line);
-        }
-    }
-
-    let mut ts_interface = String::new();
-
-    for array in arrays {
-        // PROCESS each line
-        if array.contains(&"class") {
-            let mut previous_was_class = false;
-            for item in array {
-                if previous_was_class {
-                    previous_was_class = false;
-                    let item = item.split("}").collect::<Vec<_>>()[0];
-                    ts_interface.push_str(item);
-                    ts_interface.push_str("{\n")
-                }
-                if item == "class" {
-                    previous_was_class = true;
-                    ts_interface.push_str("interface ")
-                }
-            }
-        } else {
-            let mut previous_was_access_modifier = false;
-            let mut var_type = " ";
-            let mut is_optional = false;
-            for item in array {
-                if var_type != " " {
-                    let mut item = item.to_string();
-                    let item = item.remove(0).to_lowercase().to_string() + &item;
-                    ts_interface.push_str(&item);
-                    if is_optional {
-                        ts_interface.push('?');
-                    }
-                    ts_interface.push_str(": ");
-                    ts_interface.push_str(var_type);
-                    ts_interface.push_str(";\n");
-                    var_type = " ";
-                }
-                if previous_was_access_modifier {
-                    previous_was_access_modifier = false;
-                    let split_type = item.split('?').collect::<Vec<&str>>();
-                    if item.contains(&"?") {
-                        is_optional = true;
-                    }
-                    var_type = convert_type(split_type[0]);
+    let file: CsFile = parse_csharp(&content)?;
+    let references: Vec<(String, &CsFile)> = references
+        .iter()
+        .map(|module| (module.import_path.clone(), &module.file))
+        .collect();
+    convert_file(&file, &references, options)
+}
+
+/// Converts every type in `file`. Types declared in `file` or in `references` can be
+/// used as property types and base types; those from `references` are imported from
+/// the module specifier they are paired with.
+pub fn convert_file(
+    file: &CsFile,
+    references: &[(String, &CsFile)],
+    options: &TsOptions,
+) -> Result<String, String> {
+    let mut converter = Converter {
+        options,
+        symbols: TypeScopes::default(),
+        type_imports: RefCell::new(BTreeMap::new()),
+        emitted: RefCell::new(HashSet::new()),
+        scope: RefCell::new(String::new()),
+    };
+    for decl in &file.types {
+        converter.register_types(decl, "", None);
+    }
+    for (import_path, reference) in references {
+        for decl in &reference.types {
+            converter.register_types(decl, "", Some(import_path));
+        }
+    }
+
+    let mut declarations = Vec::new();
+    for decl in &file.types {
+        collect_declarations(decl, "", &mut declarations);
+    }
+    if options.format == TsFormat::Zod {
+        // a schema can only `.extend()` a base schema that is already defined
+        declarations = converter.bases_first(declarations);
+    }
+    let mut ts_declarations: Vec<String> = Vec::new();
+    for declaration in &declarations {
+        *converter.scope.borrow_mut() = declaration.path.clone();
+        ts_declarations.push(converter.convert_declaration(declaration.decl, &declaration.name)?);
+        converter
+            .emitted
+            .borrow_mut()
+            .insert(declaration.name.clone());
+    }
+
+    let mut imports = match options.format {
+        TsFormat::Interface => Vec::new(),
+        TsFormat::Zod => vec!["import { z } from 'zod';".to_string()],
+    };
+    imports.extend(options.type_map.imports.iter().cloned());
+    let import_keyword = match options.format {
+        TsFormat::Interface => "import type",
+        TsFormat::Zod => "import",
+    };
+    for (import_path, names) in converter.type_imports.borrow().iter() {
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        imports.push(format!(
+            "{import_keyword} {{ {} }} from '{import_path}';",
+            names.join(", ")
+        ));
+    }
+    if !imports.is_empty() {
+        ts_declarations.insert(0, format!("{}\n", imports.join("\n")));
+    }
+
+    Ok(ts_declarations.join("\n"))
+}
+
+/// A C# type that is emitted as a TypeScript declaration.
+struct TsSymbol {
+    /// The (possibly flattened) TypeScript name.
+    ts_name: String,
+    /// Set when the type is declared in another module and has to be imported.
+    import_path: Option<String>,
+    is_value_type: bool,
+}
+
+struct Converter<'a> {
+    options: &'a TsOptions,
+    /// C# type path to the TypeScript declaration it is emitted as.
+    symbols: TypeScopes<TsSymbol>,
+    /// Imports needed by the types referenced so far, keyed by module.
+    type_imports: RefCell<BTreeMap<String, BTreeSet<String>>>,
+    /// Declarations of this file written out so far.
+    emitted: RefCell<HashSet<String>>,
+    /// Path of the declaration being converted, where its type names are looked up.
+    scope: RefCell<String>,
+}
+
+/// A property as it appears in the JSON.
+struct JsonMember {
+    /// The serialized name, quoted if it isn't an identifier.
+    key: String,
+    /// JSDoc block, indented for an object member.
+    doc: String,
+    /// The property type with any nullable marker removed.
+    ty: TypeRef,
+    attributes: Vec<Attribute>,
+    is_optional: bool,
+    is_null: bool,
+}
+
+impl Converter<'_> {
+    /// Records every class, struct, record and enum in `decl` so properties can refer to them.
+    /// The first declaration of a path wins, so the input file shadows references.
+    fn register_types(&mut self, decl: &TypeDecl, parent_path: &str, import_path: Option<&str>) {
+        let path = type_path(parent_path, &decl.name);
+        for nested in &decl.nested_types {
+            self.register_types(nested, &path, import_path);
+        }
+        if is_emitted(decl.kind) {
+            self.symbols.insert(
+                path.clone(),
+                TsSymbol {
+                    ts_name: flattened_name(&path),
+                    import_path: import_path.map(|path| path.to_string()),
+                    is_value_type: matches!(
+                        decl.kind,
+                        TypeKind::Enum | TypeKind::Struct | TypeKind::RecordStruct
+                    ),
+                },
+            );
+        }
+    }
+
+    /// The declared type `csharp_type` names where it is used, without noting an import.
+    fn lookup(&self, csharp_type: &TypeRef) -> Option<&TsSymbol> {
+        match csharp_type {
+            TypeRef::Named { name, .. } => self.symbols.resolve(name, &self.scope.borrow()),
+            _ => None,
+        }
+    }
+
+    /// A declared type, noting an import if it lives elsewhere: the type itself,
+    /// or its schema when generating Zod.
+    fn resolve(&self, csharp_type: &TypeRef) -> Option<&TsSymbol> {
+        let symbol = self.lookup(csharp_type)?;
+        if let Some(import_path) = &symbol.import_path {
+            let imported_name = match self.options.format {
+                TsFormat::Interface => symbol.ts_name.clone(),
+                TsFormat::Zod => format!("{}Schema", symbol.ts_name),
+            };
+            self.type_imports
+                .borrow_mut()
+                .entry(import_path.clone())
+                .or_default()
+                .insert(imported_name);
+        }
+        Some(symbol)
+    }
+
+    /// A reference to a declared class, struct or record, with its generic arguments converted.
+    fn convert_reference(&self, csharp_type: &TypeRef, type_params: &[String]) -> Option<String> {
+        let ts_name = &self.resolve(csharp_type)?.ts_name;
+        let args = csharp_type.generic_args();
+        if args.is_empty() {
+            return Some(ts_name.to_string());
+        }
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| self.convert_type(arg, type_params))
+            .collect();
+        Some(format!("{ts_name}<{}>", args.join(", ")))
+    }
+
+    fn convert_declaration(&self, decl: &TypeDecl, ts_name: &str) -> Result<String, String> {
+        match (decl.kind, self.options.format) {
+            (TypeKind::Enum, _) => self.convert_enum(decl, ts_name),
+            (_, TsFormat::Interface) => Ok(self.convert_class(decl, ts_name)),
+            (_, TsFormat::Zod) => Ok(self.convert_class_to_zod(decl, ts_name)),
+        }
+    }
+
+    /// Reorders declarations so that a base class declared in this file comes before
+    /// the classes deriving from it; otherwise the order of the file is kept.
+    fn bases_first<'d>(&self, mut pending: Vec<Declaration<'d>>) -> Vec<Declaration<'d>> {
+        let local_base = |declaration: &Declaration| {
+            *self.scope.borrow_mut() = declaration.path.clone();
+            let base = self.lookup(declaration.decl.base_types.first()?)?;
+            base.import_path.is_none().then(|| base.ts_name.clone())
+        };
+        let mut ordered: Vec<Declaration> = Vec::new();
+        while !pending.is_empty() {
+            let ready = pending
+                .iter()
+                .position(|declaration| {
+                    local_base(declaration)
+                        .is_none_or(|base| !pending.iter().any(|other| other.name == base))
+                })
+                .unwrap_or(0); // inheritance cycles don't compile in C# either
+            ordered.push(pending.remove(ready));
+        }
+        ordered
+    }
+
+    /// The public instance properties of a class as the serializer writes them:
+    /// named by the naming policy or JSON attributes, `[JsonIgnore]` ones skipped.
+    fn json_members(&self, decl: &TypeDecl) -> Vec<JsonMember> {
+        let type_doc = XmlDoc::parse(&decl.doc);
+        let mut members = Vec::new();
+        for property in decl
+            .all_properties()
+            .iter()
+            .filter(|p| p.is_public() && !p.is_static())
+        {
+            let ignore = json_ignore(&property.attributes);
+            if ignore == JsonIgnore::Always {
+                continue;
+            }
+            let (is_optional, is_null) = self.nullability(property, &decl.type_params);
+            let name = serialized_name(
+                &property.name,
+                &property.attributes,
+                self.options.naming_policy,
+            );
+            let mut property_doc = XmlDoc::parse(&property.doc);
+            if let (true, Some(param)) = (property.doc.is_empty(), type_doc.param(&property.name)) {
+                // positional record parameters are documented on the record
+                property_doc.summary = param.to_string();
+            }
+            members.push(JsonMember {
+                key: ts_property_key(&name),
+                doc: jsdoc(&property_doc, &property.attributes, "    "),
+                ty: property.ty.strip_nullable().0.clone(),
+                attributes: property.attributes.clone(),
+                is_optional: is_optional || ignore == JsonIgnore::WhenWriting,
+                is_null,
+            });
+        }
+        members
+    }
+
+    /// Emits an interface for a class, struct or record. Generic type parameters are kept
+    /// and their constraints dropped: `PagedResult<T> where T : class` becomes `PagedResult<T>`.
+    /// A base class that is itself a known DTO becomes `extends`; interfaces in the
+    /// base list and unknown base classes are left out. Properties are named as the
+    /// serializer writes them, and `[JsonIgnore]` ones are skipped.
+    fn convert_class(&self, decl: &TypeDecl, ts_name: &str) -> String {
+        let type_params = match decl.type_params.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", decl.type_params.join(", ")),
+        };
+        let extends = decl
+            .base_types
+            .first()
+            .and_then(|base| self.convert_reference(base, &decl.type_params))
+            .map(|base| format!(" extends {base}"))
+            .unwrap_or_default();
+        let type_doc = XmlDoc::parse(&decl.doc);
+        let mut ts_interface = jsdoc(&type_doc, &decl.attributes, "");
+        ts_interface.push_str(&format!(
+            "export interface {ts_name}{type_params}{extends} {{\n"
+        ));
+
+        for member in self.json_members(decl) {
+            let optional_marker = if member.is_optional { "?" } else { "" };
+            let null_union = if member.is_null { " | null" } else { "" };
+            ts_interface.push_str(&format!(
+                "{}    {}{optional_marker}: {}{null_union};\n",
+                member.doc,
+                member.key,
+                self.convert_type(&member.ty, &decl.type_params)
+            ));
+        }
+
+        ts_interface.push_str("}\n");
+        ts_interface
+    }
+
+    /// Whether a property is typed `prop?: T` and/or `prop: T | null`.
+    fn nullability(&self, property: &Property, type_params: &[String]) -> (bool, bool) {
+        let is_nullable = can_be_null(
+            property,
+            type_params,
+            self.options.nullable_context == NullableContext::Enable,
+            |ty| self.is_value_type(ty),
+        );
+
+        match (self.options.nullable_policy, is_nullable) {
+            (_, false) | (NullablePolicy::Ignore, true) => (false, false),
+            (NullablePolicy::Optional, true) => (!property.is_required(), property.is_required()),
+            (NullablePolicy::Null, true) => (false, true),
+        }
+    }
+
+    /// Enums and structs declared in the input or its references.
+    fn is_value_type(&self, csharp_type: &TypeRef) -> bool {
+        self.lookup(csharp_type).is_some_and(|s| s.is_value_type)
+    }
+
+    /// Emits an enum in the configured style. `[Flags]` enums can hold combinations
+    /// of members, so their union and const-object types widen to `number`/`string`.
+    /// Fails on a member following one whose value can't be evaluated.
+    fn convert_enum(&self, decl: &TypeDecl, ts_name: &str) -> Result<String, String> {
+        let values = enum_values(decl);
+        let members = decl
+            .enum_members
+            .iter()
+            .zip(values)
+            .map(|(member, value)| {
+                let ts_value = match self.options.enum_serialization {
+                    EnumSerialization::String => ts_string_literal(&enum_member_name(member)),
+                    EnumSerialization::Number => match (value, &member.value) {
+                        (Some(value), _) => value.to_string(),
+                        (None, Some(expression)) => expression.clone(),
+                        (None, None) => {
+                            return Err(format!(
+                                "can't evaluate the value of {}.{}; give it an explicit value",
+                                decl.name, member.name
+                            ))
+                        }
+                    },
+                };
+                Ok((member, ts_value))
+            })
+            .collect::<Result<Vec<(&EnumMember, String)>, String>>()?;
+
+        let is_flags = decl.attributes.iter().any(|a| a.is("Flags"));
+        let widened_type = match self.options.enum_serialization {
+            EnumSerialization::Number => "number",
+            EnumSerialization::String => "string",
+        };
+
+        let member_doc =
+            |member: &EnumMember| jsdoc(&XmlDoc::parse(&member.doc), &member.attributes, "    ");
+        let doc = jsdoc(&XmlDoc::parse(&decl.doc), &decl.attributes, "");
+
+        let declaration = match self.options.enum_style {
+            EnumStyle::Enum => {
+                let body: String = members
+                    .iter()
+                    .map(|(member, value)| {
+                        format!("{}    {} = {value},\n", member_doc(member), member.name)
+                    })
+                    .collect();
+                format!("{doc}export enum {ts_name} {{\n{body}}}\n")
+            }
+            EnumStyle::ConstObject => {
+                let body: String = members
+                    .iter()
+                    .map(|(member, value)| {
+                        format!("{}    {}: {value},\n", member_doc(member), member.name)
+                    })
+                    .collect();
+                let ts_type = match is_flags {
+                    true => widened_type.to_string(),
+                    false => format!("(typeof {ts_name})[keyof typeof {ts_name}]"),
+                };
+                format!("{doc}export const {ts_name} = {{\n{body}}} as const;\nexport type {ts_name} = {ts_type};\n")
+            }
+            EnumStyle::Union => {
+                let union = match (is_flags, members.is_empty()) {
+                    (true, _) => widened_type.to_string(),
+                    (false, true) => "never".to_string(),
+                    (false, false) => members
+                        .iter()
+                        .map(|(_, value)| value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" | "),
+                };
+                // members of a union can't carry their own docs
+                format!("{doc}export type {ts_name} = {union};\n")
+            }
+        };
+        Ok(match self.options.format {
+            TsFormat::Interface => declaration,
+            TsFormat::Zod => {
+                let values: Vec<String> = members.into_iter().map(|(_, v)| v).collect();
+                declaration + &self.enum_schema(ts_name, &values, is_flags)
+            }
+        })
+    }
+
+    /// Maps a C# type to TypeScript, recursing through arrays, tuples and generic
+    /// arguments to any depth. `type_params` are the generic parameters in scope,
+    /// which map to themselves.
+    fn convert_type(&self, csharp_type: &TypeRef, type_params: &[String]) -> String {
+        let convert = |ty: &TypeRef| self.convert_type(ty, type_params);
+
+        let (name, args) = match csharp_type {
+            TypeRef::Nullable(inner) => return format!("{} | null", convert(inner)),
+            // System.Text.Json writes byte arrays as base64 strings
+            TypeRef::Array(item) if item.simple_name() == Some("byte") => {
+                return "string".to_string()
+            }
+            TypeRef::Array(item) => return array_of(&convert(item)),
+            TypeRef::Tuple(items) => return tuple_of(items.iter().map(convert).collect()),
+            TypeRef::Named { name, args } => (name, args),
+        };
+        let simple_name = csharp_type.simple_name().unwrap_or(name);
+
+        match (simple_name, args.as_slice()) {
+            (name, []) if type_params.iter().any(|p| p == name) => name.to_string(),
+            ("Nullable", [inner]) => format!("{} | null", convert(inner)),
+            ("Tuple" | "ValueTuple", items) if !items.is_empty() => {
+                tuple_of(items.iter().map(convert).collect())
+            }
+            (collection, [item]) if COLLECTION_TYPES.contains(&collection) => {
+                array_of(&convert(item))
+            }
+            (dictionary, [key, value]) if DICTIONARY_TYPES.contains(&dictionary) => {
+                format!("Record<{}, {}>", convert(key), convert(value))
+            }
+            _ => match self.mapped_type(name, args) {
+                Some(ts_type) => ts_type.to_string(),
+                None => self
+                    .convert_reference(csharp_type, type_params)
+                    .unwrap_or("any".to_string()), // Default to `any` for unknown types
+            },
+        }
+    }
+
+    /// The type map's TypeScript type for a non-generic type name.
+    fn mapped_type(&self, name: &str, args: &[TypeRef]) -> Option<&str> {
+        match args {
+            [] => self.options.type_map.get(name),
+            _ => None,
+        }
+    }
+}
+
+/// Zod schemas, generated instead of interfaces with [`TsFormat::Zod`].
+impl Converter<'_> {
+    /// Emits `XSchema = z.object({...})` and `type X = z.infer<typeof XSchema>`.
+    /// A base class that is a known DTO is extended. Generic DTOs become schema
+    /// factories taking a schema per type parameter: `PagedResultSchema(CustomerDtoSchema)`.
+    fn convert_class_to_zod(&self, decl: &TypeDecl, ts_name: &str) -> String {
+        let object = match decl
+            .base_types
+            .first()
+            .and_then(|base| self.zod_reference(base, &decl.type_params))
+        {
+            Some(base) => format!("{base}.extend"),
+            None => "z.object".to_string(),
+        };
+        let mut shape = String::new();
+        for member in self.json_members(decl) {
+            let mut schema = self.zod_type(&member.ty, &decl.type_params);
+            schema.push_str(&zod_refinements(&schema, &member.attributes));
+            if member.is_null {
+                schema.push_str(".nullable()");
+            }
+            if member.is_optional {
+                schema.push_str(".optional()");
+            }
+            shape.push_str(&format!("{}    {}: {schema},\n", member.doc, member.key));
+        }
+
+        let mut zod = jsdoc(&XmlDoc::parse(&decl.doc), &decl.attributes, "");
+        match decl.type_params.is_empty() {
+            true => zod.push_str(&format!(
+                "export const {ts_name}Schema = {object}({{\n{shape}}});\n\
+                 export type {ts_name} = z.infer<typeof {ts_name}Schema>;\n"
+            )),
+            false => {
+                let type_params: Vec<String> = decl
+                    .type_params
+                    .iter()
+                    .map(|p| format!("{p} extends z.ZodTypeAny"))
+                    .collect();
+                let params: Vec<String> = decl
+                    .type_params
+                    .iter()
+                    .map(|p| format!("{}: {p}", schema_param(p)))
+                    .collect();
+                zod.push_str(&format!(
+                    "export const {ts_name}Schema = <{}>({}) =>\n    {object}({{\n{shape}}});\n",
+                    type_params.join(", "),
+                    params.join(", ")
+                ));
+            }
+        }
+        zod
+    }
+
+    /// The schema of a C# type, recursing like [`Converter::convert_type`].
+    fn zod_type(&self, csharp_type: &TypeRef, type_params: &[String]) -> String {
+        let convert = |ty: &TypeRef| self.zod_type(ty, type_params);
+        let tuple = |items: &[TypeRef]| {
+            let items: Vec<String> = items.iter().map(convert).collect();
+            format!("z.tuple([{}])", items.join(", "))
+        };
+
+        let (name, args) = match csharp_type {
+            TypeRef::Nullable(inner) => return format!("{}.nullable()", convert(inner)),
+            TypeRef::Array(item) if item.simple_name() == Some("byte") => {
+                return "z.string()".to_string()
+            }
+            TypeRef::Array(item) => return format!("z.array({})", convert(item)),
+            TypeRef::Tuple(items) => return tuple(items),
+            TypeRef::Named { name, args } => (name, args),
+        };
+        let simple_name = csharp_type.simple_name().unwrap_or(name);
+
+        match (simple_name, args.as_slice()) {
+            (name, []) if type_params.iter().any(|p| p == name) => schema_param(name),
+            ("Nullable", [inner]) => format!("{}.nullable()", convert(inner)),
+            ("Tuple" | "ValueTuple", items) if !items.is_empty() => tuple(items),
+            (collection, [item]) if COLLECTION_TYPES.contains(&collection) => {
+                format!("z.array({})", convert(item))
+            }
+            // JSON object keys are always strings
+            (dictionary, [_, value]) if DICTIONARY_TYPES.contains(&dictionary) => {
+                format!("z.record(z.string(), {})", convert(value))
+            }
+            _ => match self.mapped_type(name, args) {
+                Some("number") if INTEGER_TYPES.contains(&simple_name) => {
+                    "z.number().int()".to_string()
+                }
+                Some("string") if simple_name == "Guid" => "z.string().uuid()".to_string(),
+                Some("Date") => "z.coerce.date()".to_string(),
+                Some("bigint") => "z.coerce.bigint()".to_string(),
+                Some(ts_type @ ("number" | "string" | "boolean" | "unknown" | "any")) => {
+                    format!("z.{ts_type}()")
                 }
-                if item == "public" || item == "private" {
-                    previous_was_access_modifier = true;
+                Some(ts_type) => format!("z.custom<{ts_type}>()"),
+                None => self
+                    .zod_reference(csharp_type, type_params)
+                    .unwrap_or("z.any()".to_string()),
+            },
+        }
+    }
+
+    /// The schema of a declared class, record or enum. Schemas of this file that are
+    /// defined further down are wrapped in `z.lazy`.
+    fn zod_reference(&self, csharp_type: &TypeRef, type_params: &[String]) -> Option<String> {
+        let symbol = self.resolve(csharp_type)?;
+        let args: Vec<String> = csharp_type
+            .generic_args()
+            .iter()
+            .map(|arg| self.zod_type(arg, type_params))
+            .collect();
+        let schema = match args.is_empty() {
+            true => format!("{}Schema", symbol.ts_name),
+            false => format!("{}Schema({})", symbol.ts_name, args.join(", ")),
+        };
+        let is_defined =
+            symbol.import_path.is_some() || self.emitted.borrow().contains(&symbol.ts_name);
+        Some(match is_defined {
+            true => schema,
+            false => format!("z.lazy(() => {schema})"),
+        })
+    }
+
+    /// The schema of an enum, emitted next to the enum itself.
+    fn enum_schema(&self, ts_name: &str, values: &[String], is_flags: bool) -> String {
+        let schema = match (self.options.enum_style, is_flags) {
+            (_, true) => match self.options.enum_serialization {
+                EnumSerialization::Number => "z.number().int()".to_string(),
+                EnumSerialization::String => "z.string()".to_string(),
+            },
+            (EnumStyle::Enum | EnumStyle::ConstObject, false) => {
+                format!("z.nativeEnum({ts_name})")
+            }
+            (EnumStyle::Union, false) => {
+                let literals: Vec<String> =
+                    values.iter().map(|v| format!("z.literal({v})")).collect();
+                match literals.as_slice() {
+                    [] => "z.never()".to_string(),
+                    [literal] => literal.clone(),
+                    literals => format!("z.union([{}])", literals.join(", ")),
                 }
             }
+        };
+        format!("export const {ts_name}Schema = {schema};\n")
+    }
+}
+
+/// Zod checks for DataAnnotations on a property whose schema is `schema`. Length checks
+/// apply to strings and arrays, `[Range]` to numbers, and format checks to strings.
+fn zod_refinements(schema: &str, attributes: &[Attribute]) -> String {
+    let is_string = schema.starts_with("z.string()");
+    let is_number = schema.starts_with("z.number()");
+    let has_length = is_string || schema.starts_with("z.array(");
+    let number_arg = |attribute: &Attribute, index: usize| {
+        let arg = attribute
+            .args
+            .iter()
+            .filter(|a| a.name.is_none())
+            .nth(index)?;
+        arg.value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .map(|_| arg.value.trim().to_string())
+    };
+
+    let mut refinements = String::new();
+    for attribute in attributes {
+        if is_string && attribute.is("EmailAddress") {
+            refinements.push_str(".email()");
+        } else if is_string && attribute.is("Url") {
+            refinements.push_str(".url()");
+        } else if is_string && attribute.is("RegularExpression") {
+            if let Some(pattern) = attribute.string_arg() {
+                refinements.push_str(&format!(".regex(/{}/)", pattern.replace('/', "\\/")));
+            }
+        } else if has_length && attribute.is("MinLength") {
+            if let Some(min) = number_arg(attribute, 0) {
+                refinements.push_str(&format!(".min({min})"));
+            }
+        } else if has_length && attribute.is("MaxLength") {
+            if let Some(max) = number_arg(attribute, 0) {
+                refinements.push_str(&format!(".max({max})"));
+            }
+        } else if is_string && attribute.is("StringLength") {
+            // a `MinimumLength` given by a constant isn't known here; skip it
+            let min = attribute
+                .args
+                .iter()
+                .find(|a| a.name.as_deref() == Some("MinimumLength"))
+                .and_then(|a| a.value.trim().parse::<usize>().ok());
+            if let Some(min) = min {
+                refinements.push_str(&format!(".min({min})"));
+            }
+            if let Some(max) = number_arg(attribute, 0) {
+                refinements.push_str(&format!(".max({max})"));
+            }
+        } else if is_number && attribute.is("Range") {
+            // `[Range(typeof(decimal), "0", "1")]` and named bounds aren't numeric; skip them
+            if let (Some(min), Some(max)) = (number_arg(attribute, 0), number_arg(attribute, 1)) {
+                refinements.push_str(&format!(".min({min}).max({max})"));
+            }
+        }
+    }
+    refinements
+}
+
+/// The value parameter of a schema factory that stands for type parameter `T`.
+fn schema_param(type_param: &str) -> String {
+    format!("{}Schema", lowercase_first(type_param))
+}
+
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A type to emit and the name it is emitted as.
+#[derive(Clone)]
+pub struct Declaration<'d> {
+    /// Flattened name: `OrderDtoItem` for `OrderDto.Item`.
+    pub name: String,
+    /// Path of the type in its file, `OrderDto.Item`, as keyed by [`TypeScopes`].
+    pub path: String,
+    pub decl: &'d TypeDecl,
+}
+
+/// A type and everything nested in it, in declaration order. Nested types are
+/// flattened into their parent's name, so `OrderDto.Item` becomes `OrderDtoItem`.
+pub fn collect_declarations<'d>(
+    decl: &'d TypeDecl,
+    parent_path: &str,
+    declarations: &mut Vec<Declaration<'d>>,
+) {
+    let path = type_path(parent_path, &decl.name);
+    if is_emitted(decl.kind) {
+        declarations.push(Declaration {
+            name: flattened_name(&path),
+            path: path.clone(),
+            decl,
+        });
+    }
+    for nested in &decl.nested_types {
+        collect_declarations(nested, &path, declarations);
+    }
+}
+
+fn flattened_name(path: &str) -> String {
+    path.replace('.', "")
+}
+
+/// Kinds that become a TypeScript declaration.
+pub fn is_emitted(kind: TypeKind) -> bool {
+    matches!(
+        kind,
+        TypeKind::Class
+            | TypeKind::Struct
+            | TypeKind::Record
+            | TypeKind::RecordStruct
+            | TypeKind::Enum
+    )
+}
+
+/// A JSDoc block from an XML doc comment, with `[Obsolete]` as `@deprecated`.
+/// Empty when there is nothing to say; a single line stays on one line.
+fn jsdoc(doc: &XmlDoc, attributes: &[Attribute], indent: &str) -> String {
+    let mut lines: Vec<String> = doc.summary.lines().map(str::to_string).collect();
+    for (tag, text) in [("@remarks", &doc.remarks), ("@example", &doc.example)] {
+        if !text.is_empty() {
+            lines.push(tag.to_string());
+            lines.extend(text.lines().map(str::to_string));
+        }
+    }
+    if let Some(obsolete) = attributes.iter().find(|a| a.is("Obsolete")) {
+        lines.push(match obsolete.string_arg() {
+            Some(message) => format!("@deprecated {message}"),
+            None => "@deprecated".to_string(),
+        });
+    }
+
+    let lines: Vec<String> = lines.iter().map(|l| l.replace("*/", "*\\/")).collect();
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{indent}/** {line} */\n"),
+        lines => {
+            let body: String = lines
+                .iter()
+                .map(|line| match line.is_empty() {
+                    true => format!("{indent} *\n"),
+                    false => format!("{indent} * {line}\n"),
+                })
+                .collect();
+            format!("{indent}/**\n{body}{indent} */\n")
         }
-        // println!("{:?}", array);
     }
+}
 
-    ts_interface.push_str("}\n");
+/// A property name as written in an interface, quoted unless it is a valid identifier.
+fn ts_property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    match is_identifier {
+        true => name.to_string(),
+        false => ts_string_literal(name),
+    }
+}
 
-    // ts_interface
-    ts_interface.to_string()
+fn tuple_of(items: Vec<String>) -> String {
+    format!("[{}]", items.join(", "))
 }
 
-fn convert_type(csharp_type: &str) -> &str {
-    match csharp_type {
-        "int" => "number",
-        "float" => "number",
-        "double" => "number",
-        "decimal" => "number",
-        "string" => "string",
-        "bool" => "boolean",
-        "DateTime" => "Date",
-        "List<int>" => "number[]",
-        "List<string>" => "string[]",
-        _ => "any", // Default to `any` for unknown types
+/// `T[]`, parenthesizing union element types: `(number | null)[]`.
+fn array_of(item: &str) -> String {
+    let mut depth = 0;
+    let top_level_union = item.chars().any(|c| {
+        match c {
+            '<' | '[' | '(' | '{' => depth += 1,
+            '>' | ']' | ')' | '}' => depth -= 1,
+            _ => {}
+        }
+        c == '|' && depth == 0
+    });
+    match top_level_union {
+        true => format!("({item})[]"),
+        false => format!("{item}[]"),
     }
 }
 
+fn ts_string_literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::csharp_syntax::AttributeArg;
+
+    fn convert_to_typescript_interface(
+        dto_content: &str,
+        options: &TsOptions,
+    ) -> Result<String, String> {
+        csharp_dto_to_ts_interface(dto_content.to_string(), &[], options)
+    }
     fn remove_whitespace(input: &str) -> String {
         input.chars().filter(|&c| !c.is_whitespace()).collect()
     }
@@ -115,14 +900,15 @@ mod tests {
         "#;
 
         let expected_ts_interface = r#"
-            interface SimpleDto {
+            export interface SimpleDto {
                 id: number;
                 name?: string;
                 isActive: boolean;
             }
             "#;
 
-        let ts_interface = convert_to_typescript_interface(csharp_dto);
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
         assert_eq!(
             remove_whitespace(ts_interface.as_str()),
             remove_whitespace(expected_ts_interface)
@@ -142,7 +928,7 @@ mod tests {
             "#;
 
         let expected_ts_interface = r#"
-                interface ComplexDto {
+                export interface ComplexDto {
                     id: number;
                     name: string;
                     isActive: boolean;
@@ -151,7 +937,8 @@ mod tests {
                 }
                 "#;
 
-        let ts_interface = convert_to_typescript_interface(csharp_dto);
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
 
         assert_eq!(
             remove_whitespace(ts_interface.as_str()),
@@ -168,12 +955,13 @@ mod tests {
             "#;
 
         let expected_ts_interface = r#"
-                interface UnknownTypeDto {
+                export interface UnknownTypeDto {
                     customProperty: any;
                 }
                 "#;
 
-        let ts_interface = convert_to_typescript_interface(csharp_dto);
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
         assert_eq!(
             remove_whitespace(ts_interface.as_str()),
             remove_whitespace(expected_ts_interface)
@@ -188,15 +976,1164 @@ mod tests {
             "#;
 
         let expected_ts_interface = r#"
-            interface EmptyDto {
+            export interface EmptyDto {
             }
             "#;
 
-        let ts_interface = convert_to_typescript_interface(csharp_dto);
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_attributes_comments_and_initializers() {
+        let csharp_dto = r#"
+                using System.Text.Json.Serialization;
+
+                namespace Acme.Dtos;
+
+                /// <summary>An order</summary>
+                [Serializable]
+                public class OrderDto
+                {
+                    // internal id, not { exposed }
+                    [JsonPropertyName("id")]
+                    public int Id { get; set; }
+                    public List<string> Tags { get; set; } = new();
+                    public string Display => $"{Id} {{}}";
+                    public string Name { get; set; } = "}";
+                    private int _count;
+                    public static int Counter { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                /** An order */
+                export interface OrderDto {
+                    id: number;
+                    tags: string[];
+                    display: string;
+                    name: string;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_two_properties_on_one_line() {
+        let csharp_dto = r#"
+                public class PairDto { public int Left { get; set; } public bool? Right { get; init; } }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface PairDto {
+                    left: number;
+                    right?: boolean;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_methods_and_constructors_are_ignored() {
+        let csharp_dto = r#"
+                public class WithBehaviourDto
+                {
+                    public WithBehaviourDto(int id) { Id = id; }
+                    public int Id { get; }
+                    public override string ToString() => $"Dto {Id}";
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface WithBehaviourDto {
+                    id: number;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_is_an_error() {
+        let csharp_dto = r#"
+                public class BrokenDto {
+                    public string Name { get; set; } = "unterminated;
+                }
+            "#;
+
+        assert!(convert_to_typescript_interface(csharp_dto, &TsOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_multiple_classes_in_one_file() {
+        let csharp_dto = r#"
+                namespace Acme.Dtos
+                {
+                    public class CreateOrderRequest
+                    {
+                        public string Customer { get; set; }
+                    }
+
+                    public class CreateOrderResponse
+                    {
+                        public int OrderId { get; set; }
+                    }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface CreateOrderRequest {
+                    customer: string;
+                }
+                export interface CreateOrderResponse {
+                    orderId: number;
+                }
+                "#;
 
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
         assert_eq!(
             remove_whitespace(ts_interface.as_str()),
             remove_whitespace(expected_ts_interface)
         );
     }
+
+    #[test]
+    fn test_nested_classes_are_flattened() {
+        let csharp_dto = r#"
+                public class OrderDto
+                {
+                    public int Id { get; set; }
+
+                    public class Line
+                    {
+                        public int Quantity { get; set; }
+
+                        public class Discount
+                        {
+                            public decimal Amount { get; set; }
+                        }
+                    }
+
+                    public string Status { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface OrderDto {
+                    id: number;
+                    status: string;
+                }
+                export interface OrderDtoLine {
+                    quantity: number;
+                }
+                export interface OrderDtoLineDiscount {
+                    amount: number;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_enum_styles_with_numeric_serialization() {
+        let csharp_dto = r#"
+                public enum OrderStatus { Draft, Submitted = 5, Shipped }
+
+                public class OrderDto
+                {
+                    public OrderStatus Status { get; set; }
+                    public OrderStatus? PreviousStatus { get; set; }
+                }
+            "#;
+
+        let expected_enum = r#"
+                export enum OrderStatus {
+                    Draft = 0,
+                    Submitted = 5,
+                    Shipped = 6,
+                }
+                export interface OrderDto {
+                    status: OrderStatus;
+                    previousStatus?: OrderStatus;
+                }
+                "#;
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_enum)
+        );
+
+        let options = TsOptions {
+            enum_style: EnumStyle::ConstObject,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert!(
+            remove_whitespace(ts_interface.as_str()).starts_with(&remove_whitespace(
+                r#"
+                export const OrderStatus = {
+                    Draft: 0,
+                    Submitted: 5,
+                    Shipped: 6,
+                } as const;
+                export type OrderStatus = (typeof OrderStatus)[keyof typeof OrderStatus];
+                "#
+            ))
+        );
+
+        let options = TsOptions {
+            enum_style: EnumStyle::Union,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert!(ts_interface.starts_with("export type OrderStatus = 0 | 5 | 6;\n"));
+    }
+
+    #[test]
+    fn test_member_after_unevaluated_value_is_an_error() {
+        let csharp_dto = "public enum Level { Low = Limits.Min, High }";
+        let error = convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap_err();
+        assert!(error.contains("Level.High"), "{error}");
+        let options = TsOptions {
+            format: TsFormat::Zod,
+            enum_style: EnumStyle::Union,
+            ..TsOptions::default()
+        };
+        assert!(convert_to_typescript_interface(csharp_dto, &options).is_err());
+    }
+
+    #[test]
+    fn test_string_enum_serialization_honors_member_names() {
+        let csharp_dto = r#"
+                public enum Priority
+                {
+                    Low,
+                    [JsonStringEnumMemberName("very-high")]
+                    VeryHigh,
+                    [EnumMember(Value = "urgent")]
+                    Urgent,
+                }
+            "#;
+
+        let options = TsOptions {
+            enum_style: EnumStyle::Union,
+            enum_serialization: EnumSerialization::String,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            ts_interface,
+            "export type Priority = \"Low\" | \"very-high\" | \"urgent\";\n"
+        );
+
+        let options = TsOptions {
+            enum_style: EnumStyle::Enum,
+            enum_serialization: EnumSerialization::String,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(
+                r#"export enum Priority { Low = "Low", VeryHigh = "very-high", Urgent = "urgent", }"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_flags_enum_widens_union_type() {
+        let csharp_dto = r#"
+                [Flags]
+                public enum Permissions { None = 0, Read = 1 << 0, Write = 1 << 1, All = Read | Write }
+            "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(
+                "export enum Permissions { None = 0, Read = 1, Write = 2, All = 3, }"
+            )
+        );
+
+        let options = TsOptions {
+            enum_style: EnumStyle::Union,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(ts_interface, "export type Permissions = number;\n");
+    }
+
+    #[test]
+    fn test_structs_are_emitted_and_not_nullable() {
+        let csharp_dto = r#"
+                public class ShipmentDto
+                {
+                    public Money Cost { get; set; }
+                    public Money? Refund { get; set; }
+                }
+                public struct Money
+                {
+                    public decimal Amount { get; set; }
+                    public string Currency { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface ShipmentDto {
+                    cost: Money;
+                    refund?: Money;
+                }
+                export interface Money {
+                    amount: number;
+                    currency?: string;
+                }
+                "#;
+
+        let options = TsOptions {
+            nullable_context: NullableContext::Disable,
+            ..Default::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_same_named_nested_types_resolve_in_their_parent() {
+        let csharp_dto = r#"
+                public class CreateOrderRequest
+                {
+                    public List<Item> Items { get; set; }
+                    public class Item { public int ProductId { get; set; } }
+                }
+                public class CreateOrderResponse
+                {
+                    public List<Item> Items { get; set; }
+                    public CreateOrderRequest.Item? Requested { get; set; }
+                    public class Item { public decimal Price { get; set; } }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface CreateOrderRequest {
+                    items: CreateOrderRequestItem[];
+                }
+                export interface CreateOrderRequestItem {
+                    productId: number;
+                }
+                export interface CreateOrderResponse {
+                    items: CreateOrderResponseItem[];
+                    requested?: CreateOrderRequestItem;
+                }
+                export interface CreateOrderResponseItem {
+                    price: number;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_nested_enum_is_referenced_by_flattened_name() {
+        let csharp_dto = r#"
+                public class ShipmentDto
+                {
+                    public enum Carrier { Ups, Fedex }
+                    public Carrier Via { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface ShipmentDto {
+                    via: ShipmentDtoCarrier;
+                }
+                export enum ShipmentDtoCarrier {
+                    Ups = 0,
+                    Fedex = 1,
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_positional_and_nominal_records() {
+        let csharp_dto = r#"
+                public record CustomerDto(int Id, string Name, string? Email);
+
+                public sealed record class AddressDto
+                {
+                    public required string Street { get; init; }
+                    public string? City { get; init; }
+                }
+
+                public readonly record struct Money(decimal Amount, string Currency)
+                {
+                    public bool IsZero => Amount == 0;
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface CustomerDto {
+                    id: number;
+                    name: string;
+                    email?: string;
+                }
+                export interface AddressDto {
+                    street: string;
+                    city?: string;
+                }
+                export interface Money {
+                    amount: number;
+                    currency: string;
+                    isZero: boolean;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_record_with_base_record_arguments() {
+        let csharp_dto = r#"
+                public record PersonDto(string Name);
+                public record EmployeeDto(string Name, int EmployeeNumber) : PersonDto(Name);
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface PersonDto {
+                    name: string;
+                }
+                export interface EmployeeDto extends PersonDto {
+                    name: string;
+                    employeeNumber: number;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_generic_dtos() {
+        let csharp_dto = r#"
+                public class PagedResult<T> where T : class
+                {
+                    public List<T> Items { get; set; }
+                    public int TotalCount { get; set; }
+                }
+
+                public class ApiResponse<TData, TError> : IResponse where TData : notnull
+                {
+                    public TData? Data { get; set; }
+                    public Dictionary<string, List<TError>> Errors { get; set; }
+                    public List<int?> Codes { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface PagedResult<T> {
+                    items: T[];
+                    totalCount: number;
+                }
+                export interface ApiResponse<TData, TError> {
+                    data?: TData;
+                    errors: Record<string, TError[]>;
+                    codes: (number | null)[];
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_generic_positional_record() {
+        let csharp_dto = r#"
+                public record Envelope<T>(T Payload, Dictionary<string, List<T>> Related);
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface Envelope<T> {
+                    payload: T;
+                    related: Record<string, T[]>;
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    fn ts_type_of(csharp_type: &str) -> String {
+        let file = parse_csharp(&format!(
+            "public class Probe<T> {{ public {csharp_type} Value {{ get; set; }} }}"
+        ))
+        .unwrap();
+        let options = TsOptions::default();
+        let converter = Converter {
+            options: &options,
+            symbols: TypeScopes::default(),
+            type_imports: RefCell::new(BTreeMap::new()),
+            emitted: RefCell::new(HashSet::new()),
+            scope: RefCell::new(String::new()),
+        };
+        let decl = &file.types[0];
+        converter.convert_type(&decl.all_properties()[0].ty, &decl.type_params)
+    }
+
+    #[test]
+    fn test_collection_types() {
+        let cases = [
+            ("int[]", "number[]"),
+            ("string[][]", "string[][]"),
+            ("byte[]", "string"),
+            ("IEnumerable<int>", "number[]"),
+            ("ICollection<T>", "T[]"),
+            ("IReadOnlyList<bool>", "boolean[]"),
+            ("HashSet<string>", "string[]"),
+            ("System.Collections.Generic.List<decimal>", "number[]"),
+            ("List<int?>[]", "(number | null)[][]"),
+            ("List<List<List<T>>>", "T[][][]"),
+        ];
+        for (csharp_type, expected) in cases {
+            assert_eq!(ts_type_of(csharp_type), expected, "{csharp_type}");
+        }
+    }
+
+    #[test]
+    fn test_dictionary_tuple_and_nullable_types() {
+        let cases = [
+            ("Dictionary<string, decimal>", "Record<string, number>"),
+            ("IReadOnlyDictionary<int, List<T>>", "Record<number, T[]>"),
+            (
+                "Dictionary<string, Dictionary<string, int[]>>",
+                "Record<string, Record<string, number[]>>",
+            ),
+            ("Nullable<int>", "number | null"),
+            ("List<Nullable<bool>>", "(boolean | null)[]"),
+            ("(int, string)", "[number, string]"),
+            ("(int Id, List<T> Items)[]", "[number, T[]][]"),
+            ("Tuple<int, bool>", "[number, boolean]"),
+            ("Unknown<int>", "any"),
+        ];
+        for (csharp_type, expected) in cases {
+            assert_eq!(ts_type_of(csharp_type), expected, "{csharp_type}");
+        }
+    }
+
+    #[test]
+    fn test_nullable_wrapper_makes_property_optional() {
+        let csharp_dto = r#"
+                public class MeasurementDto
+                {
+                    public Nullable<int> Reading { get; set; }
+                    public IEnumerable<(string Label, double Value)> Points { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface MeasurementDto {
+                    reading?: number;
+                    points: [string, number][];
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_builtin_type_map_defaults() {
+        let cases = [
+            ("Guid", "string"),
+            ("System.Guid", "string"),
+            ("long", "number"),
+            ("short", "number"),
+            ("byte", "number"),
+            ("DateTimeOffset", "Date"),
+            ("DateOnly", "string"),
+            ("TimeOnly", "string"),
+            ("TimeSpan", "string"),
+            ("char", "string"),
+            ("object", "unknown"),
+            ("List<Guid>", "string[]"),
+        ];
+        for (csharp_type, expected) in cases {
+            assert_eq!(ts_type_of(csharp_type), expected, "{csharp_type}");
+        }
+    }
+
+    #[test]
+    fn test_custom_type_map_and_imports() {
+        let csharp_dto = r#"
+                public class AuditDto
+                {
+                    public Guid Id { get; set; }
+                    public long Sequence { get; set; }
+                    public DateTime At { get; set; }
+                    public int Count { get; set; }
+                }
+            "#;
+
+        let options = TsOptions {
+            type_map: TsTypeMap::from_yaml(
+                r#"
+types:
+  Guid: UUID
+  long: bigint
+  DateTime: string
+imports:
+  - "import type { UUID } from './uuid';"
+"#,
+            )
+            .unwrap(),
+            ..TsOptions::default()
+        };
+
+        let expected_ts_interface = r#"
+                import type { UUID } from './uuid';
+
+                export interface AuditDto {
+                    id: UUID;
+                    sequence: bigint;
+                    at: string;
+                    count: number;
+                }
+                "#;
+
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_inheritance_and_references_within_a_file() {
+        let csharp_dto = r#"
+                public class CustomerDto : IHasId
+                {
+                    public int Id { get; set; }
+                    public AddressDto? Address { get; set; }
+                }
+
+                public class CustomerDetailDto : CustomerDto, IAuditable
+                {
+                    public List<OrderDto> Orders { get; set; }
+                    public PagedResult<OrderDto> History { get; set; }
+                }
+
+                public class AddressDto { public string Street { get; set; } }
+                public class OrderDto { public int Number { get; set; } }
+                public class PagedResult<T> { public List<T> Items { get; set; } }
+                public class CustomerPage : PagedResult<CustomerDto> { }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface CustomerDto {
+                    id: number;
+                    address?: AddressDto;
+                }
+                export interface CustomerDetailDto extends CustomerDto {
+                    orders: OrderDto[];
+                    history: PagedResult<OrderDto>;
+                }
+                export interface AddressDto {
+                    street: string;
+                }
+                export interface OrderDto {
+                    number: number;
+                }
+                export interface PagedResult<T> {
+                    items: T[];
+                }
+                export interface CustomerPage extends PagedResult<CustomerDto> {
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_types_from_referenced_files_are_imported() {
+        let references = vec![
+            TsModule::from_file(
+                "Dtos/CustomerDto.cs",
+                "public class CustomerDto { public int Id { get; set; } } public enum Tier { Gold }",
+            )
+            .unwrap(),
+            TsModule::from_file(
+                "Dtos/Shared/Money.cs",
+                "public record Money(decimal Amount, string Currency);",
+            )
+            .unwrap(),
+        ];
+        let csharp_dto = r#"
+                public class CustomerDetailDto : CustomerDto
+                {
+                    public Tier Tier { get; set; }
+                    public List<Money> Balances { get; set; }
+                    public UnknownDto Other { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                import type { CustomerDto, Tier } from './CustomerDto';
+                import type { Money } from './Money';
+
+                export interface CustomerDetailDto extends CustomerDto {
+                    tier: Tier;
+                    balances: Money[];
+                    other: any;
+                }
+                "#;
+
+        let ts_interface =
+            csharp_dto_to_ts_interface(csharp_dto.to_string(), &references, &TsOptions::default())
+                .unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_json_attributes_and_naming_policy() {
+        let csharp_dto = r#"
+                public record OrderDto([property: JsonPropertyName("order_no")] int OrderNumber)
+                {
+                    public string CustomerName { get; set; }
+                    [JsonProperty("Total Amount")]
+                    public decimal Total { get; set; }
+                    [JsonIgnore]
+                    public string InternalNote { get; set; }
+                    [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)]
+                    public string Coupon { get; set; }
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                export interface OrderDto {
+                    order_no: number;
+                    "customer-name": string;
+                    "Total Amount": number;
+                    coupon?: string;
+                }
+                "#;
+
+        let options = TsOptions {
+            naming_policy: NamingPolicy::Kebab,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_nullable_contexts_required_and_initializers() {
+        let csharp_dto = r#"
+                #nullable disable
+                public class LegacyDto
+                {
+                    public string Name { get; set; }
+                    public int Count { get; set; }
+                    public Status Status { get; set; }
+                    public List<string> Tags { get; set; } = new();
+                    [Required]
+                    public string Code { get; set; }
+                }
+                #nullable enable
+                public class ModernDto
+                {
+                    public string Name { get; set; } = null!;
+                    public string? Nickname { get; set; }
+                    [Required]
+                    public string? Email { get; set; }
+                    public required string? Phone { get; set; }
+                    public int? Age { get; set; }
+                }
+                public enum Status { Active }
+            "#;
+
+        let expected = [
+            (
+                NullablePolicy::Optional,
+                r#"
+                export interface LegacyDto {
+                    name?: string;
+                    count: number;
+                    status: Status;
+                    tags: string[];
+                    code: string;
+                }
+                export interface ModernDto {
+                    name: string;
+                    nickname?: string;
+                    email: string;
+                    phone: string | null;
+                    age?: number;
+                }
+                "#,
+            ),
+            (
+                NullablePolicy::Null,
+                r#"
+                export interface LegacyDto {
+                    name: string | null;
+                    count: number;
+                    status: Status;
+                    tags: string[];
+                    code: string;
+                }
+                export interface ModernDto {
+                    name: string;
+                    nickname: string | null;
+                    email: string;
+                    phone: string | null;
+                    age: number | null;
+                }
+                "#,
+            ),
+            (
+                NullablePolicy::Ignore,
+                r#"
+                export interface LegacyDto {
+                    name: string;
+                    count: number;
+                    status: Status;
+                    tags: string[];
+                    code: string;
+                }
+                export interface ModernDto {
+                    name: string;
+                    nickname: string;
+                    email: string;
+                    phone: string;
+                    age: number;
+                }
+                "#,
+            ),
+        ];
+
+        for (nullable_policy, expected_ts_interface) in expected {
+            let options = TsOptions {
+                nullable_policy,
+                enum_style: EnumStyle::Union,
+                ..TsOptions::default()
+            };
+            let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+            let ts_interface = ts_interface.replace("export type Status = 0;\n", "");
+            assert_eq!(
+                remove_whitespace(ts_interface.as_str()),
+                remove_whitespace(expected_ts_interface),
+                "{nullable_policy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_wide_nullable_context() {
+        let csharp_dto = "public record PersonDto(string Name, DateTime Born, (int, int) Range);";
+        let options = TsOptions {
+            nullable_context: NullableContext::Disable,
+            ..TsOptions::default()
+        };
+        let ts_interface = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(
+                "export interface PersonDto { name?: string; born: Date; range: [number, number]; }"
+            )
+        );
+    }
+
+    #[test]
+    fn test_xml_doc_comments_become_jsdoc() {
+        let csharp_dto = r#"
+                /// <summary>
+                /// A customer of the shop.
+                /// </summary>
+                /// <remarks>Loaded from <see cref="T:Acme.Customer"/>.</remarks>
+                /// <param name="Id">Primary key.</param>
+                public record CustomerDto(int Id)
+                {
+                    /// <summary>Display name.</summary>
+                    public string Name { get; set; }
+
+                    [Obsolete("Use Name instead")]
+                    public string FullName { get; set; }
+
+                    /// <summary>Account state.</summary>
+                    [Obsolete]
+                    public Status Status { get; set; }
+                }
+
+                /// <summary>Account state.</summary>
+                public enum Status
+                {
+                    /// <summary>Can place orders.</summary>
+                    Active,
+                    Closed,
+                }
+            "#;
+
+        let expected_ts_interface = r#"
+                /**
+                 * A customer of the shop.
+                 * @remarks
+                 * Loaded from {@link Customer}.
+                 */
+                export interface CustomerDto {
+                    /** Primary key. */
+                    id: number;
+                    /** Display name. */
+                    name: string;
+                    /** @deprecated Use Name instead */
+                    fullName: string;
+                    /**
+                     * Account state.
+                     * @deprecated
+                     */
+                    status: Status;
+                }
+                /** Account state. */
+                export enum Status {
+                    /** Can place orders. */
+                    Active = 0,
+                    Closed = 1,
+                }
+                "#;
+
+        let ts_interface =
+            convert_to_typescript_interface(csharp_dto, &TsOptions::default()).unwrap();
+        assert_eq!(
+            remove_whitespace(ts_interface.as_str()),
+            remove_whitespace(expected_ts_interface)
+        );
+    }
+
+    #[test]
+    fn test_string_length_skips_non_numeric_minimum() {
+        let string_length = |minimum: &str| Attribute {
+            name: "StringLength".to_string(),
+            args: vec![
+                AttributeArg {
+                    name: None,
+                    value: "50".to_string(),
+                },
+                AttributeArg {
+                    name: Some("MinimumLength".to_string()),
+                    value: minimum.to_string(),
+                },
+            ],
+        };
+        assert_eq!(
+            zod_refinements("z.string()", &[string_length("2")]),
+            ".min(2).max(50)"
+        );
+        assert_eq!(
+            zod_refinements("z.string()", &[string_length("Limits.Min")]),
+            ".max(50)"
+        );
+    }
+
+    #[test]
+    fn test_zod_schemas() {
+        let csharp_dto = r#"
+                public class CustomerDetailDto : CustomerDto
+                {
+                    [EmailAddress, MaxLength(200)]
+                    public string? Email { get; set; }
+                    [Range(0, 120)]
+                    public int Age { get; set; }
+                    [StringLength(50, MinimumLength = 2)]
+                    public string Nickname { get; set; }
+                    public decimal Balance { get; set; }
+                    public Guid ExternalId { get; set; }
+                    public DateTime CreatedAt { get; set; }
+                    public Status Status { get; set; }
+                    [MinLength(1)]
+                    public List<AddressDto> Addresses { get; set; }
+                    public Dictionary<string, bool> Flags { get; set; }
+                    public PagedResult<AddressDto> History { get; set; }
+                }
+
+                /// <summary>A customer.</summary>
+                public class CustomerDto
+                {
+                    [RegularExpression(@"^[A-Z]+/\d+$")]
+                    public string Code { get; set; }
+                }
+
+                public record AddressDto(string Street);
+
+                public class PagedResult<T>
+                {
+                    public List<T> Items { get; set; }
+                }
+
+                public enum Status { Active = 1, Closed = 2 }
+            "#;
+
+        let expected_zod = r#"
+                import { z } from 'zod';
+
+                /** A customer. */
+                export const CustomerDtoSchema = z.object({
+                    code: z.string().regex(/^[A-Z]+\/\d+$/),
+                });
+                export type CustomerDto = z.infer<typeof CustomerDtoSchema>;
+
+                export const CustomerDetailDtoSchema = CustomerDtoSchema.extend({
+                    email: z.string().email().max(200).optional(),
+                    age: z.number().int().min(0).max(120),
+                    nickname: z.string().min(2).max(50),
+                    balance: z.number(),
+                    externalId: z.string().uuid(),
+                    createdAt: z.coerce.date(),
+                    status: z.lazy(() => StatusSchema),
+                    addresses: z.array(z.lazy(() => AddressDtoSchema)).min(1),
+                    flags: z.record(z.string(), z.boolean()),
+                    history: z.lazy(() => PagedResultSchema(z.lazy(() => AddressDtoSchema))),
+                });
+                export type CustomerDetailDto = z.infer<typeof CustomerDetailDtoSchema>;
+
+                export const AddressDtoSchema = z.object({
+                    street: z.string(),
+                });
+                export type AddressDto = z.infer<typeof AddressDtoSchema>;
+
+                export const PagedResultSchema = <T extends z.ZodTypeAny>(tSchema: T) =>
+                    z.object({
+                        items: z.array(tSchema),
+                    });
+
+                export enum Status {
+                    Active = 1,
+                    Closed = 2,
+                }
+                export const StatusSchema = z.nativeEnum(Status);
+                "#;
+
+        let options = TsOptions {
+            format: TsFormat::Zod,
+            ..TsOptions::default()
+        };
+        let zod = convert_to_typescript_interface(csharp_dto, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(zod.as_str()),
+            remove_whitespace(expected_zod)
+        );
+    }
+
+    #[test]
+    fn test_zod_union_enums_and_imported_schemas() {
+        let references =
+            vec![TsModule::from_file("CustomerDto.cs", "public class CustomerDto { }").unwrap()];
+        let csharp_dto = r#"
+                public enum Tier { Gold, Silver }
+                public class OrderDto
+                {
+                    public CustomerDto Customer { get; set; }
+                    public Tier Tier { get; set; }
+                }
+            "#;
+
+        let expected_zod = r#"
+                import { z } from 'zod';
+                import { CustomerDtoSchema } from './CustomerDto';
+
+                export type Tier = 0 | 1;
+                export const TierSchema = z.union([z.literal(0), z.literal(1)]);
+
+                export const OrderDtoSchema = z.object({
+                    customer: CustomerDtoSchema,
+                    tier: TierSchema,
+                });
+                export type OrderDto = z.infer<typeof OrderDtoSchema>;
+                "#;
+
+        let options = TsOptions {
+            format: TsFormat::Zod,
+            enum_style: EnumStyle::Union,
+            ..TsOptions::default()
+        };
+        let zod =
+            csharp_dto_to_ts_interface(csharp_dto.to_string(), &references, &options).unwrap();
+        assert_eq!(
+            remove_whitespace(zod.as_str()),
+            remove_whitespace(expected_zod)
+        );
+    }
 }
@@ -0,0 +1,694 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::{
+    crud_query::{
+        filters::{criteria_properties, non_nullable, plural},
+        project_config::CrudProjectConfig,
+        project_files::{print_summary, write_project_files, ProjectFile},
+    },
+    utils::{
+        csharp_parser::parse_csharp,
+        csharp_syntax::{TypeDecl, TypeKind},
+    },
+    FieldWithType,
+};
+
+/// How the endpoints are declared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ApiStyle {
+    /// An `[ApiController]` class deriving from the configured base
+    #[default]
+    Controller,
+    /// A `Map{Entity}Endpoints` extension method mapping a route group
+    MinimalApi,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ApiOptions {
+    pub style: ApiStyle,
+    /// `BASE_NAMESPACE`, which the layout namespaces are appended to.
+    pub base_namespace: Option<String>,
+}
+
+/// The DTO the endpoints take and return instead of the entity.
+struct Dto {
+    name: String,
+    namespace: Option<String>,
+    /// Public instance properties, in declaration order.
+    properties: Vec<String>,
+    /// Type of each of `properties`.
+    property_types: Vec<String>,
+    /// Primary constructor parameters of a positional record, passed when building one.
+    constructor_parameters: Vec<String>,
+}
+
+impl Dto {
+    /// The first public class, record or record struct of the file.
+    fn parse(source: &str) -> Result<Self, String> {
+        let file = parse_csharp(source)?;
+        let decl = file
+            .all_types()
+            .into_iter()
+            .find(|decl| {
+                decl.is_public()
+                    && matches!(
+                        decl.kind,
+                        TypeKind::Class | TypeKind::Record | TypeKind::RecordStruct
+                    )
+            })
+            .ok_or("no public DTO type found")?;
+        let (properties, property_types) = dto_properties(decl).into_iter().unzip();
+        Ok(Self {
+            name: decl.name.clone(),
+            namespace: file.namespace.clone(),
+            properties,
+            property_types,
+            constructor_parameters: match decl.is_record() {
+                true => decl
+                    .primary_parameters
+                    .iter()
+                    .flatten()
+                    .map(|parameter| parameter.name.clone())
+                    .collect(),
+                false => Vec::new(),
+            },
+        })
+    }
+
+    fn property_type(&self, name: &str) -> Option<&str> {
+        let index = self
+            .properties
+            .iter()
+            .position(|property| property == name)?;
+        Some(&self.property_types[index])
+    }
+}
+
+/// Name and type of the public instance properties.
+fn dto_properties(decl: &TypeDecl) -> Vec<(String, String)> {
+    decl.all_properties()
+        .iter()
+        .filter(|property| property.is_public() && !property.is_static())
+        .map(|property| (property.name.clone(), property.ty.to_string()))
+        .collect()
+}
+
+/// Names shared by every endpoint.
+struct Endpoints<'a> {
+    entity_name: &'a str,
+    entity_id_name: &'a str,
+    id_type: String,
+    dto: Option<Dto>,
+    /// Name and type of the criteria's filter properties.
+    criteria_properties: Vec<(String, String)>,
+    config: &'a CrudProjectConfig,
+}
+
+impl Endpoints<'_> {
+    /// `{id:int}`, or just `{id}` for types without a route constraint.
+    fn id_route(&self) -> String {
+        match self.id_type.as_str() {
+            "int" | "Int32" => "{id:int}".to_string(),
+            "long" | "Int64" => "{id:long}".to_string(),
+            "Guid" => "{id:guid}".to_string(),
+            _ => "{id}".to_string(),
+        }
+    }
+
+    /// Type of the request and response bodies.
+    fn body_type(&self) -> &str {
+        match &self.dto {
+            Some(dto) => &dto.name,
+            None => self.entity_name,
+        }
+    }
+
+    /// `entity`, or `ToDto(entity)` with a DTO.
+    fn response(&self) -> &'static str {
+        match self.dto {
+            Some(_) => "ToDto(entity)",
+            None => "entity",
+        }
+    }
+
+    /// Statements turning the request body `body` into `entity` for `POST`.
+    fn new_entity(&self) -> &'static str {
+        match self.dto {
+            Some(_) => "var entity = ToEntity(body);",
+            None => "var entity = body;",
+        }
+    }
+
+    /// What `PUT` saves: the stored entity the DTO was copied onto, or the body itself.
+    fn updated_entity(&self) -> &'static str {
+        match self.dto {
+            Some(_) => "entity",
+            None => "body",
+        }
+    }
+
+    fn usings(&self, base_namespace: Option<&str>, minimal_api: bool) -> String {
+        let mut namespaces: Vec<String> = match minimal_api {
+            true => vec![
+                "Microsoft.AspNetCore.Builder".to_string(),
+                "Microsoft.AspNetCore.Http".to_string(),
+                "Microsoft.AspNetCore.Routing".to_string(),
+            ],
+            false => vec!["Microsoft.AspNetCore.Mvc".to_string()],
+        };
+        let has_lists = self
+            .criteria_properties
+            .iter()
+            .any(|(_, ty)| ty.starts_with("List<"));
+        if minimal_api && has_lists {
+            namespaces.insert(0, "System.Linq".to_string());
+        }
+        if let Some(base_namespace) = base_namespace {
+            let mut layouts = vec![
+                &self.config.query_criteria,
+                &self.config.repository_interface,
+            ];
+            if minimal_api {
+                // the query parameters sort by the sortable enum
+                layouts.push(&self.config.sortable_field);
+            }
+            for layout in layouts {
+                namespaces.push(match layout.namespace.as_str() {
+                    "" => base_namespace.to_string(),
+                    namespace => format!("{base_namespace}.{namespace}"),
+                });
+            }
+        }
+        namespaces.extend(self.dto.as_ref().and_then(|dto| dto.namespace.clone()));
+        let mut usings = String::new();
+        for namespace in namespaces {
+            let using = format!("using {namespace};\n");
+            if !usings.contains(&using) {
+                usings.push_str(&using);
+            }
+        }
+        usings
+    }
+
+    /// `ToDto` and `ToEntity`, mapping the DTO's properties by name.
+    fn mappings(&self, indent: &str) -> String {
+        let Some(dto) = &self.dto else {
+            return "".to_string();
+        };
+        let entity_name = self.entity_name;
+        // a positional record's other properties are set by an initializer after its constructor
+        let initialized: Vec<String> = dto
+            .properties
+            .iter()
+            .filter(|property| !dto.constructor_parameters.contains(property))
+            .cloned()
+            .collect();
+        let to_dto = object_creation(
+            &dto.name,
+            &dto.constructor_parameters,
+            &initialized,
+            "entity",
+            indent,
+        );
+        let settable: Vec<String> = dto
+            .properties
+            .iter()
+            .filter(|property| *property != self.entity_id_name)
+            .cloned()
+            .collect();
+        let to_entity = object_creation(entity_name, &[], &settable, "dto", indent);
+        format!(
+            r#"
+{indent}private static {dto_name} ToDto({entity_name} entity) =>
+{indent}    {to_dto};
+
+{indent}private static {entity_name} ToEntity({dto_name} dto) =>
+{indent}    {to_entity};
+"#,
+            dto_name = dto.name
+        )
+    }
+
+    /// Statements applying the `PUT` body to the stored `entity`: the DTO's properties
+    /// are copied onto it, or the body's id must match the route.
+    fn update_statements(&self, indent: &str, bad_request: &str) -> String {
+        match &self.dto {
+            Some(dto) => dto
+                .properties
+                .iter()
+                .filter(|property| *property != self.entity_id_name)
+                .map(|property| format!("{indent}entity.{property} = body.{property};\n"))
+                .collect(),
+            None => format!(
+                "{indent}if (id != body.{id_name})\n{indent}{{\n{indent}    return {bad_request};\n{indent}}}\n",
+                id_name = self.entity_id_name
+            ),
+        }
+    }
+
+    fn controller(&self, base_namespace: Option<&str>) -> String {
+        let entity_name = self.entity_name;
+        let usings = self.usings(base_namespace, false);
+        let namespace = self.config.api.namespace_declaration(base_namespace);
+        let class_name = format!("{}Controller", plural(entity_name));
+        let base = self.config.api.base_clause(entity_name);
+        let methods = &self.config.repository_methods;
+        let (query, get, add, update, delete) = (
+            &methods.query,
+            &methods.get,
+            &methods.add,
+            &methods.update,
+            &methods.delete,
+        );
+        let (id_type, id_route, body_type) = (&self.id_type, self.id_route(), self.body_type());
+        let (response, new_entity) = (self.response(), self.new_entity());
+        let id_name = self.entity_id_name;
+        let updated = self.update_statements("        ", "BadRequest()");
+        let entity = self.updated_entity();
+        let lookup = match self.dto {
+            Some(_) => format!(
+                r#"        var entity = await _repository.{get}(id);
+        if (entity == null)
+        {{
+            return NotFound();
+        }}
+"#
+            ),
+            None => "".to_string(),
+        };
+        let mappings = self.mappings("    ");
+        format!(
+            r#"{usings}
+{namespace}
+
+[ApiController]
+[Route("api/[controller]")]
+public class {class_name}{base}
+{{
+    private readonly I{entity_name}Repository _repository;
+
+    public {class_name}(I{entity_name}Repository repository)
+    {{
+        _repository = repository;
+    }}
+
+    [HttpGet]
+    public async Task<IActionResult> Get([FromQuery] {entity_name}QueryCriteria criteria)
+    {{
+        return Ok(await _repository.{query}(criteria));
+    }}
+
+    [HttpGet("{id_route}")]
+    public async Task<IActionResult> GetById({id_type} id)
+    {{
+        var entity = await _repository.{get}(id);
+        if (entity == null)
+        {{
+            return NotFound();
+        }}
+        return Ok({response});
+    }}
+
+    [HttpPost]
+    public async Task<IActionResult> Create({body_type} body)
+    {{
+        {new_entity}
+        await _repository.{add}(entity);
+        return CreatedAtAction(nameof(GetById), new {{ id = entity.{id_name} }}, {response});
+    }}
+
+    [HttpPut("{id_route}")]
+    public async Task<IActionResult> Update({id_type} id, {body_type} body)
+    {{
+{lookup}{updated}        await _repository.{update}({entity});
+        return NoContent();
+    }}
+
+    [HttpDelete("{id_route}")]
+    public async Task<IActionResult> Delete({id_type} id)
+    {{
+        var entity = await _repository.{get}(id);
+        if (entity == null)
+        {{
+            return NotFound();
+        }}
+        await _repository.{delete}(entity);
+        return NoContent();
+    }}
+{mappings}}}
+"#
+        )
+    }
+
+    fn minimal_api(&self, base_namespace: Option<&str>) -> String {
+        let entity_name = self.entity_name;
+        let usings = self.usings(base_namespace, true);
+        let namespace = self.config.api.namespace_declaration(base_namespace);
+        let route = plural(entity_name).to_lowercase();
+        let methods = &self.config.repository_methods;
+        let (query, get, add, update, delete) = (
+            &methods.query,
+            &methods.get,
+            &methods.add,
+            &methods.update,
+            &methods.delete,
+        );
+        let (id_type, id_route, body_type) = (&self.id_type, self.id_route(), self.body_type());
+        let (response, new_entity) = (self.response(), self.new_entity());
+        let id_name = self.entity_id_name;
+        let repository = format!("I{entity_name}Repository repository");
+        let updated = self.update_statements("            ", "Results.BadRequest()");
+        let entity = self.updated_entity();
+        let lookup = match self.dto {
+            Some(_) => format!(
+                r#"            var entity = await repository.{get}(id);
+            if (entity == null)
+            {{
+                return Results.NotFound();
+            }}
+"#
+            ),
+            None => "".to_string(),
+        };
+        let mappings = self.mappings("    ");
+        let (query_parameters, criteria) = self.query_parameters();
+        format!(
+            r#"{usings}
+{namespace}
+
+public static class {entity_name}Endpoints
+{{
+    public static RouteGroupBuilder Map{entity_name}Endpoints(this IEndpointRouteBuilder app)
+    {{
+        var group = app.MapGroup("/api/{route}");
+
+        group.MapGet("/", async ([AsParameters] {entity_name}QueryParameters query, {repository}) =>
+        {{
+{criteria}            return Results.Ok(await repository.{query}(criteria));
+        }});
+
+        group.MapGet("/{id_route}", async ({id_type} id, {repository}) =>
+        {{
+            var entity = await repository.{get}(id);
+            return entity == null ? Results.NotFound() : Results.Ok({response});
+        }})
+        .WithName("Get{entity_name}ById");
+
+        group.MapPost("/", async ({body_type} body, {repository}) =>
+        {{
+            {new_entity}
+            await repository.{add}(entity);
+            return Results.CreatedAtRoute("Get{entity_name}ById", new {{ id = entity.{id_name} }}, {response});
+        }});
+
+        group.MapPut("/{id_route}", async ({id_type} id, {body_type} body, {repository}) =>
+        {{
+{lookup}{updated}            await repository.{update}({entity});
+            return Results.NoContent();
+        }});
+
+        group.MapDelete("/{id_route}", async ({id_type} id, {repository}) =>
+        {{
+            var entity = await repository.{get}(id);
+            if (entity == null)
+            {{
+                return Results.NotFound();
+            }}
+            await repository.{delete}(entity);
+            return Results.NoContent();
+        }});
+
+        return group;
+    }}
+{mappings}{query_parameters}}}
+"#
+        )
+    }
+
+    /// `[AsParameters]` can't bind the criteria itself: its `SortCriteria` would be read
+    /// from the body, which a `GET` doesn't have. The query string is bound to a class of
+    /// the filter properties and the sort instead, and copied onto the criteria.
+    /// Returns that class and the statements building `criteria` from `query`.
+    fn query_parameters(&self) -> (String, String) {
+        let entity_name = self.entity_name;
+        let mut properties = String::new();
+        let mut assignments = String::new();
+        for (name, ty) in &self.criteria_properties {
+            // query strings bind to arrays, not lists
+            match ty.strip_prefix("List<").and_then(|ty| ty.strip_suffix('>')) {
+                Some(item) => {
+                    properties.push_str(&format!(
+                        "        public {item}[]? {name} {{ get; set; }}\n"
+                    ));
+                    assignments.push_str(&format!(
+                        "                {name} = query.{name}?.ToList(),\n"
+                    ));
+                }
+                None => {
+                    properties.push_str(&format!("        public {ty}? {name} {{ get; set; }}\n"));
+                    assignments.push_str(&format!("                {name} = query.{name},\n"));
+                }
+            }
+        }
+        let class = format!(
+            r#"
+    public class {entity_name}QueryParameters
+    {{
+{properties}        public {entity_name}SortableField? SortBy {{ get; set; }}
+        public SortOrder? SortOrder {{ get; set; }}
+    }}
+"#
+        );
+        let criteria = format!(
+            r#"            var criteria = new {entity_name}QueryCriteria
+            {{
+{assignments}            }};
+            if (query.SortBy.HasValue)
+            {{
+                criteria.SortCriteria = new() {{ {{ query.SortBy.Value, query.SortOrder ?? SortOrder.Ascending }} }};
+            }}
+"#
+        );
+        (class, criteria)
+    }
+}
+
+/// `new {type_name}(source.A, ...) { B = source.B, ... }`, the initializer over several lines.
+fn object_creation(
+    type_name: &str,
+    arguments: &[String],
+    properties: &[String],
+    source: &str,
+    indent: &str,
+) -> String {
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| format!("{source}.{argument}"))
+        .collect();
+    let call = match (arguments.is_empty(), properties.is_empty()) {
+        (true, false) => format!("new {type_name}"),
+        _ => format!("new {type_name}({})", arguments.join(", ")),
+    };
+    if properties.is_empty() {
+        return call;
+    }
+    let assignments: String = properties
+        .iter()
+        .map(|property| format!("{indent}        {property} = {source}.{property},\n"))
+        .collect();
+    format!("{call}\n{indent}    {{\n{assignments}{indent}    }}")
+}
+
+/// The id's type: that of the sortable field or, failing that, the DTO property named after it.
+fn id_type(
+    entity_id_name: &str,
+    sortable_fields: Option<&[FieldWithType]>,
+    dto: Option<&Dto>,
+) -> Result<String, String> {
+    sortable_fields
+        .unwrap_or_default()
+        .iter()
+        .find(|field| field.field == entity_id_name)
+        .map(|field| field.field_type.as_str())
+        .or_else(|| dto?.property_type(entity_id_name))
+        .map(|ty| non_nullable(ty).to_string())
+        .ok_or(format!(
+            "the type of {entity_id_name} is unknown; give it with -t {entity_id_name},<type> or --entity-file"
+        ))
+}
+
+/// Generates the API endpoints of an entity's query criteria repository, writing them
+/// under `BASE_PROJECT_ROUTE` when it is set.
+pub fn run(
+    entity_name: String,
+    entity_id_name: Option<String>,
+    dto: Option<String>,
+    sortable_fields: Option<Vec<FieldWithType>>,
+    options: &ApiOptions,
+    force: bool,
+    config: &CrudProjectConfig,
+) -> Result<String, String> {
+    let entity_id_name = entity_id_name.ok_or("the entity id name (-e) is required")?;
+
+    let base_project_route = std::env::var("BASE_PROJECT_ROUTE")
+        .ok()
+        .filter(|route| !route.is_empty());
+
+    let dto = dto.as_deref().map(Dto::parse).transpose()?;
+    let id_type = id_type(&entity_id_name, sortable_fields.as_deref(), dto.as_ref())?;
+    let endpoints = Endpoints {
+        entity_name: &entity_name,
+        entity_id_name: &entity_id_name,
+        id_type,
+        dto,
+        criteria_properties: sortable_fields
+            .iter()
+            .flatten()
+            .flat_map(|field| criteria_properties(field, &config.filters))
+            .collect(),
+        config,
+    };
+    let (file_name, content) = match options.style {
+        ApiStyle::Controller => (
+            format!("{}Controller.cs", plural(&entity_name)),
+            endpoints.controller(options.base_namespace.as_deref()),
+        ),
+        ApiStyle::MinimalApi => (
+            format!("{entity_name}Endpoints.cs"),
+            endpoints.minimal_api(options.base_namespace.as_deref()),
+        ),
+    };
+    if let Some(route) = &base_project_route {
+        let file = ProjectFile {
+            path: PathBuf::from(config.api.path(&file_name)),
+            content: content.clone(),
+            patched: false,
+        };
+        print_summary(
+            &write_project_files(Path::new(route), &[file], force)?,
+            force,
+        );
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints<'a>(dto: Option<&str>, config: &'a CrudProjectConfig) -> Endpoints<'a> {
+        Endpoints {
+            entity_name: "Customer",
+            entity_id_name: "CustomerId",
+            id_type: "int".to_string(),
+            dto: dto.map(|source| Dto::parse(source).unwrap()),
+            criteria_properties: Vec::new(),
+            config,
+        }
+    }
+
+    #[test]
+    fn test_controller() {
+        let config = CrudProjectConfig::default();
+        let controller = endpoints(None, &config).controller(Some("Acme"));
+        assert!(controller.starts_with(
+            "using Microsoft.AspNetCore.Mvc;\nusing Acme.Core.Domain;\nusing Acme.Infrastructure.Interfaces;\n\nnamespace Acme.Api.Controllers;\n"
+        ));
+        assert!(controller.contains("public class CustomersController : ControllerBase\n"));
+        assert!(controller.contains(
+            "public async Task<IActionResult> Get([FromQuery] CustomerQueryCriteria criteria)\n    {\n        return Ok(await _repository.GetPagedAsync(criteria));"
+        ));
+        assert!(controller.contains(
+            "[HttpGet(\"{id:int}\")]\n    public async Task<IActionResult> GetById(int id)"
+        ));
+        assert!(controller.contains("new { id = entity.CustomerId }, entity);"));
+        assert!(controller.contains(
+            "if (id != body.CustomerId)\n        {\n            return BadRequest();\n        }\n        await _repository.UpdateAsync(body);"
+        ));
+        assert!(controller.contains("await _repository.DeleteAsync(entity);"));
+        assert!(!controller.contains("ToDto"));
+    }
+
+    #[test]
+    fn test_minimal_api_with_dto() {
+        let config = CrudProjectConfig::default();
+        let dto = r#"namespace Acme.Api.Dtos;
+
+public record CustomerDto(int CustomerId, string Name);
+"#;
+        let endpoints = endpoints(Some(dto), &config).minimal_api(Some("Acme"));
+        assert!(endpoints.contains("using Acme.Api.Dtos;\n"));
+        assert!(endpoints.contains(
+            "public static RouteGroupBuilder MapCustomerEndpoints(this IEndpointRouteBuilder app)"
+        ));
+        assert!(endpoints.contains("app.MapGroup(\"/api/customers\")"));
+        assert!(endpoints.contains(
+            "group.MapGet(\"/\", async ([AsParameters] CustomerQueryParameters query, ICustomerRepository repository) =>"
+        ));
+        assert!(endpoints.contains("group.MapPut(\"/{id:int}\", async (int id, CustomerDto body, ICustomerRepository repository) =>"));
+        assert!(endpoints.contains("            entity.Name = body.Name;\n            await repository.UpdateAsync(entity);"));
+        assert!(!endpoints.contains("entity.CustomerId = body.CustomerId"));
+        assert!(endpoints.contains(
+            "private static CustomerDto ToDto(Customer entity) =>\n        new CustomerDto(entity.CustomerId, entity.Name);"
+        ));
+        assert!(
+            endpoints.contains("new Customer\n        {\n            Name = dto.Name,\n        };")
+        );
+    }
+
+    #[test]
+    fn test_minimal_api_binds_the_query_without_the_sort_criteria() {
+        let config = CrudProjectConfig::default();
+        let mut endpoints = endpoints(None, &config);
+        endpoints.criteria_properties = vec![
+            ("LastName".to_string(), "string".to_string()),
+            ("CustomerIds".to_string(), "List<int>".to_string()),
+        ];
+        let endpoints = endpoints.minimal_api(Some("Acme"));
+        assert!(endpoints.starts_with("using System.Linq;\n"));
+        assert!(endpoints.contains(
+            "            var criteria = new CustomerQueryCriteria\n            {\n                LastName = query.LastName,\n                CustomerIds = query.CustomerIds?.ToList(),\n            };\n            if (query.SortBy.HasValue)\n            {\n                criteria.SortCriteria = new() { { query.SortBy.Value, query.SortOrder ?? SortOrder.Ascending } };\n            }\n            return Results.Ok(await repository.GetPagedAsync(criteria));"
+        ));
+        assert!(endpoints.contains(
+            "    public class CustomerQueryParameters\n    {\n        public string? LastName { get; set; }\n        public int[]? CustomerIds { get; set; }\n        public CustomerSortableField? SortBy { get; set; }\n        public SortOrder? SortOrder { get; set; }\n    }\n}\n"
+        ));
+        assert!(!endpoints.contains("[AsParameters] CustomerQueryCriteria"));
+    }
+
+    #[test]
+    fn test_id_type() {
+        let dto =
+            Dto::parse("public record struct CustomerDto(Guid CustomerId, string Name);").unwrap();
+        assert_eq!(dto.name, "CustomerDto");
+        assert_eq!(id_type("CustomerId", None, Some(&dto)).unwrap(), "Guid");
+        let fields = vec![FieldWithType {
+            field: "CustomerId".to_string(),
+            field_type: "long?".to_string(),
+        }];
+        assert_eq!(
+            id_type("CustomerId", Some(&fields), Some(&dto)).unwrap(),
+            "long"
+        );
+        assert!(id_type("CustomerId", None, None).is_err());
+        assert!(id_type("Id", Some(&fields), Some(&dto)).is_err());
+    }
+
+    #[test]
+    fn test_positional_record_with_body_properties() {
+        let config = CrudProjectConfig::default();
+        let dto = r#"public record CustomerDto(int CustomerId, string Name)
+{
+    public string? Email { get; init; }
+}
+"#;
+        let controller = endpoints(Some(dto), &config).controller(None);
+        assert!(controller.contains(
+            "private static CustomerDto ToDto(Customer entity) =>\n        new CustomerDto(entity.CustomerId, entity.Name)\n        {\n            Email = entity.Email,\n        };"
+        ));
+        assert!(
+            controller.contains("            Name = dto.Name,\n            Email = dto.Email,\n")
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use std::{collections::HashMap, str::FromStr};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{
+    utils::csharp_types::{COLLECTION_TYPES, VALUE_TYPES},
+    FieldWithType,
+};
+
+/// How a field is filtered on, and so which criteria properties it gets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum FilterMode {
+    /// `X`, matched with `==`
+    #[default]
+    Equals,
+    /// `XFrom` and `XTo`, both inclusive. Numbers, dates and times
+    Range,
+    /// `X`, matched with `string.Contains`
+    Contains,
+    /// `X`, matched with `string.StartsWith`
+    StartsWith,
+    /// `Xs`, a list of accepted values
+    In,
+}
+
+/// A field's filter mode as given on the command line, IE: `CreatedAt=range`.
+#[derive(Clone, Debug)]
+pub struct FieldFilter {
+    pub field: String,
+    pub mode: FilterMode,
+}
+
+impl FromStr for FieldFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, mode) = s
+            .split_once('=')
+            .ok_or(format!("expected `field=mode`, found `{s}`"))?;
+        Ok(Self {
+            field: field.trim().to_string(),
+            mode: FilterMode::from_str(mode.trim(), true)?,
+        })
+    }
+}
+
+/// Numbers, dates and times: the types `Range` filters compare with `>=` and `<=`.
+const ORDERED_TYPES: [&str; 27] = [
+    "byte",
+    "Byte",
+    "sbyte",
+    "SByte",
+    "short",
+    "Int16",
+    "ushort",
+    "UInt16",
+    "int",
+    "Int32",
+    "uint",
+    "UInt32",
+    "long",
+    "Int64",
+    "ulong",
+    "UInt64",
+    "float",
+    "Single",
+    "double",
+    "Double",
+    "decimal",
+    "Decimal",
+    "DateTime",
+    "DateTimeOffset",
+    "DateOnly",
+    "TimeOnly",
+    "TimeSpan",
+];
+
+/// How `ApplyCriteria` filters on a criteria property. `{field}` in the templates is
+/// replaced by the property name.
+struct FilterRule {
+    /// Whether the rule handles a property type, given without its nullable marker.
+    applies: fn(&str) -> bool,
+    /// When the criteria property is set.
+    condition: &'static str,
+    /// The entity filter applied when it is.
+    predicate: &'static str,
+}
+
+/// Filter rules shared by both CRUD programs, first match wins. Criteria properties
+/// are nullable, so value types are tested with `HasValue`; enums and any other type
+/// fall through to a null check.
+const FILTER_RULES: [FilterRule; 4] = [
+    FilterRule {
+        applies: is_string,
+        condition: "!string.IsNullOrEmpty(criteria.{field})",
+        predicate: "e => e.{field} == criteria.{field}",
+    },
+    FilterRule {
+        applies: is_collection,
+        condition: "criteria.{field} != null && criteria.{field}.Any()",
+        predicate: "e => criteria.{field}.Contains(e.{field})",
+    },
+    FilterRule {
+        applies: |ty| VALUE_TYPES.contains(&simple_name(ty)),
+        condition: "criteria.{field}.HasValue",
+        predicate: "e => e.{field} == criteria.{field}",
+    },
+    FilterRule {
+        applies: |_| true,
+        condition: "criteria.{field} != null",
+        predicate: "e => e.{field} == criteria.{field}",
+    },
+];
+
+/// The type without a trailing `?` or `Nullable<>` wrapper.
+pub fn non_nullable(field_type: &str) -> &str {
+    let field_type = field_type.trim();
+    if let Some(inner) = field_type.strip_suffix('?') {
+        return inner.trim_end();
+    }
+    ["Nullable<", "System.Nullable<"]
+        .iter()
+        .find_map(|prefix| field_type.strip_prefix(prefix)?.strip_suffix('>'))
+        .map(str::trim)
+        .unwrap_or(field_type)
+}
+
+/// The type name without namespace or generic arguments, e.g. `List` for `System.Collections.Generic.List<int>`.
+fn simple_name(ty: &str) -> &str {
+    let name = ty.split('<').next().unwrap_or(ty).trim();
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+fn is_collection(ty: &str) -> bool {
+    ty.ends_with("[]") || (ty.contains('<') && COLLECTION_TYPES.contains(&simple_name(ty)))
+}
+
+fn is_string(ty: &str) -> bool {
+    matches!(ty, "string" | "String")
+}
+
+/// Naive English plural of a property name, IE: `Id` -> `Ids`, `Status` -> `Statuses`.
+pub fn plural(name: &str) -> String {
+    match name.strip_suffix('y') {
+        Some(stem) if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) => format!("{stem}ies"),
+        _ if name.ends_with(['s', 'x', 'z']) || name.ends_with("ch") || name.ends_with("sh") => {
+            format!("{name}es")
+        }
+        _ => format!("{name}s"),
+    }
+}
+
+/// Fails on a filter mode that doesn't suit its field's type. Fields without a mode are `Equals`.
+pub fn check_filter_modes(
+    fields: &[FieldWithType],
+    modes: &HashMap<String, FilterMode>,
+) -> Result<(), String> {
+    for field in fields {
+        let mode = mode_of(field, modes);
+        let ty = non_nullable(&field.field_type);
+        let suits = match mode {
+            FilterMode::Equals => true,
+            FilterMode::Range => ORDERED_TYPES.contains(&simple_name(ty)),
+            FilterMode::Contains | FilterMode::StartsWith => is_string(ty),
+            FilterMode::In => !is_collection(ty),
+        };
+        if !suits {
+            return Err(format!(
+                "{} filter can't be used on {} of type {}",
+                mode.to_possible_value().unwrap().get_name(),
+                field.field,
+                field.field_type
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn mode_of(field: &FieldWithType, modes: &HashMap<String, FilterMode>) -> FilterMode {
+    modes.get(&field.field).copied().unwrap_or_default()
+}
+
+/// Name and type, without the nullable marker, of the criteria properties filtering on a field.
+pub fn criteria_properties(
+    field: &FieldWithType,
+    modes: &HashMap<String, FilterMode>,
+) -> Vec<(String, String)> {
+    let (name, ty) = (&field.field, non_nullable(&field.field_type));
+    match mode_of(field, modes) {
+        FilterMode::Range => vec![
+            (format!("{name}From"), ty.to_string()),
+            (format!("{name}To"), ty.to_string()),
+        ],
+        FilterMode::In => vec![(plural(name), format!("List<{ty}>"))],
+        _ => vec![(name.clone(), ty.to_string())],
+    }
+}
+
+/// The `.WhereIf(condition, predicate)` calls filtering on a field.
+pub fn where_if(field: &FieldWithType, modes: &HashMap<String, FilterMode>) -> Vec<String> {
+    let name = &field.field;
+    match mode_of(field, modes) {
+        FilterMode::Equals => {
+            let rule = FILTER_RULES
+                .iter()
+                .find(|rule| (rule.applies)(non_nullable(&field.field_type)))
+                .expect("the last filter rule applies to every type");
+            vec![format!(
+                ".WhereIf({}, {})",
+                rule.condition.replace("{field}", name),
+                rule.predicate.replace("{field}", name)
+            )]
+        }
+        FilterMode::Range => vec![
+            format!(".WhereIf(criteria.{name}From.HasValue, e => e.{name} >= criteria.{name}From)"),
+            format!(".WhereIf(criteria.{name}To.HasValue, e => e.{name} <= criteria.{name}To)"),
+        ],
+        FilterMode::Contains => vec![format!(
+            ".WhereIf(!string.IsNullOrEmpty(criteria.{name}), e => e.{name}.Contains(criteria.{name}))"
+        )],
+        FilterMode::StartsWith => vec![format!(
+            ".WhereIf(!string.IsNullOrEmpty(criteria.{name}), e => e.{name}.StartsWith(criteria.{name}))"
+        )],
+        FilterMode::In => {
+            let list = plural(name);
+            vec![format!(
+                ".WhereIf(criteria.{list} != null && criteria.{list}.Any(), e => criteria.{list}.Contains(e.{name}))"
+            )]
+        }
+    }
+}
+
+/// The `ApplyCriteria` override filtering `query` on every field, each in its mode.
+pub fn apply_criteria(
+    entity_name: &str,
+    fields: &[FieldWithType],
+    modes: &HashMap<String, FilterMode>,
+) -> String {
+    let query: String = fields
+        .iter()
+        .flat_map(|field| where_if(field, modes))
+        .map(|where_if| format!("\n\t\t{where_if}"))
+        .collect();
+    format!(
+        r#"
+    protected override IQueryable<{entity_name}> ApplyCriteria(IQueryable<{entity_name}> query, {entity_name}QueryCriteria criteria)
+    {{
+        return query{query};
+    }}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, field_type: &str) -> FieldWithType {
+        FieldWithType {
+            field: name.to_string(),
+            field_type: field_type.to_string(),
+        }
+    }
+
+    fn filter(field_type: &str) -> String {
+        where_if(&field("X", field_type), &HashMap::new()).join("")
+    }
+
+    #[test]
+    fn test_filter_rules() {
+        assert_eq!(
+            filter("string"),
+            ".WhereIf(!string.IsNullOrEmpty(criteria.X), e => e.X == criteria.X)"
+        );
+        for ty in [
+            "bool",
+            "long",
+            "decimal",
+            "Guid",
+            "DateTime",
+            "DateOnly",
+            "int?",
+            "Nullable<Guid>",
+        ] {
+            assert_eq!(
+                filter(ty),
+                ".WhereIf(criteria.X.HasValue, e => e.X == criteria.X)",
+                "{ty}"
+            );
+        }
+        assert_eq!(
+            filter("List<int>"),
+            ".WhereIf(criteria.X != null && criteria.X.Any(), e => criteria.X.Contains(e.X))"
+        );
+        assert_eq!(filter("Guid[]"), filter("IEnumerable<Guid>"));
+        assert_eq!(
+            filter("CustomerStatus?"),
+            ".WhereIf(criteria.X != null, e => e.X == criteria.X)"
+        );
+    }
+
+    #[test]
+    fn test_filter_modes() {
+        let fields = vec![
+            field("CreatedAt", "DateTime?"),
+            field("Name", "string"),
+            field("Id", "int"),
+            field("Category", "string"),
+        ];
+        let modes: HashMap<String, FilterMode> = [
+            "CreatedAt=range",
+            "Name=starts-with",
+            "Id=in",
+            "Category=in",
+        ]
+        .iter()
+        .map(|s| FieldFilter::from_str(s).unwrap())
+        .map(|f| (f.field, f.mode))
+        .collect();
+        check_filter_modes(&fields, &modes).unwrap();
+
+        let properties: Vec<(String, String)> = fields
+            .iter()
+            .flat_map(|field| criteria_properties(field, &modes))
+            .collect();
+        assert_eq!(
+            properties,
+            vec![
+                ("CreatedAtFrom".to_string(), "DateTime".to_string()),
+                ("CreatedAtTo".to_string(), "DateTime".to_string()),
+                ("Name".to_string(), "string".to_string()),
+                ("Ids".to_string(), "List<int>".to_string()),
+                ("Categories".to_string(), "List<string>".to_string()),
+            ]
+        );
+
+        let query = apply_criteria("Customer", &fields, &modes);
+        assert!(query.contains(
+            ".WhereIf(criteria.CreatedAtFrom.HasValue, e => e.CreatedAt >= criteria.CreatedAtFrom)"
+        ));
+        assert!(query.contains(
+            ".WhereIf(criteria.CreatedAtTo.HasValue, e => e.CreatedAt <= criteria.CreatedAtTo)"
+        ));
+        assert!(query.contains(
+            ".WhereIf(!string.IsNullOrEmpty(criteria.Name), e => e.Name.StartsWith(criteria.Name))"
+        ));
+        assert!(query.contains(
+            ".WhereIf(criteria.Ids != null && criteria.Ids.Any(), e => criteria.Ids.Contains(e.Id))"
+        ));
+    }
+
+    #[test]
+    fn test_filter_mode_must_suit_the_type() {
+        let modes = HashMap::from([("Name".to_string(), FilterMode::Range)]);
+        assert!(check_filter_modes(&[field("Name", "string")], &modes).is_err());
+        let modes = HashMap::from([("Age".to_string(), FilterMode::Contains)]);
+        assert!(check_filter_modes(&[field("Age", "int")], &modes).is_err());
+        assert!(FieldFilter::from_str("Name").is_err());
+        assert!(FieldFilter::from_str("Name=fuzzy").is_err());
+    }
+}
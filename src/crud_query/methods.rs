@@ -1,45 +1,36 @@
 // CREATE enum sortable fields
 // public enum entity_nameSortableField
 // {
-//     entity_ID
-//     EffectiveDate,
-//     ExpireDate,
+//     entity_ID,
+//     sortable_fields...
 // }
 
-use crate::FieldWithType;
+use crate::{crud_query::{filters::criteria_properties, project_config::CrudProjectConfig, sorting::{default_sort, sortable_members, SortOptions}}, FieldWithType};
 
 pub fn generate_sortable_field_enum(
     entity_name: String,
     entity_id_name: String,
     namespace: Option<String>,
     sortable_fields: Option<Vec<FieldWithType>>,
+    config: &CrudProjectConfig,
+    sort: &SortOptions,
 ) -> String {
-    let namespace = match namespace {
-        Some(base_namespace) => format!("namespace {base_namespace}.Core.Domain;"),
-        None => "".to_string(),
-    };
+    let namespace = config.sortable_field.namespace_declaration(namespace.as_deref());
+    let base = config.sortable_field.base_clause(&entity_name);
 
-    let sortable_fields: String = match sortable_fields {
-        Some(fields) => fields
-            .clone()
-            .into_iter()
-            .map(|field| return format!("{}, \n", field.field))
-            .collect(),
-        None => "".to_string(),
-    };
-    return format!(
+    let members: String = sortable_members(&entity_id_name, sortable_fields.as_deref(), sort)
+        .into_iter()
+        .map(|member| format!("\n    {member},"))
+        .collect();
+    format!(
         r#"
 {namespace}
 
-public enum {entity_name}SortableField
-{{
-    {entity_id_name},
-    EffectiveDate,
-    ExpireDate, 
-    {sortable_fields}
+public enum {entity_name}SortableField{base}
+{{{members}
 }}
 "#,
-    );
+    )
 }
 
 pub fn generate_query_criteria(
@@ -47,52 +38,58 @@ pub fn generate_query_criteria(
     entity_id_name: String,
     namespace: Option<String>,
     sortable_fields: Option<Vec<FieldWithType>>,
+    config: &CrudProjectConfig,
+    sort: &SortOptions,
 ) -> String {
+    let default_sort = default_sort(&entity_id_name, sort);
+    let (default_sort_field, default_sort_order) = (default_sort.field, default_sort.order);
     let sortable_fields: String = match sortable_fields {
         Some(fields) => fields
             .clone()
             .into_iter()
-            .map(|field| {
-                return format!(
+            .flat_map(|field| criteria_properties(&field, &config.filters))
+            .map(|(name, field_type)| {
+                format!(
                     "public {}? {} {{ get; set; }} \n",
-                    field.field_type, field.field
-                );
+                    field_type, name
+                )
             })
             .collect(),
         None => "".to_string(),
     };
 
-    let namespace = match namespace {
-        Some(base_namespace) => format!("namespace {base_namespace}.Core.Domain;"),
-        None => "".to_string(),
-    };
+    let namespace = config.query_criteria.namespace_declaration(namespace.as_deref());
+    let base = config.query_criteria.base_clause(&entity_name);
 
-    return format!(
+    format!(
         r#"
 {namespace}
 
-public class  {entity_name}QueryCriteria : DefaultQueryCriteria<{entity_name}SortableField>
+public class  {entity_name}QueryCriteria{base}
 {{
     {sortable_fields}
     public override SortCriteria<{entity_name}SortableField> SortCriteria {{ get; set; }} = new()
     {{
-        {{ {entity_name}SortableField.{entity_id_name}, SortOrder.Ascending }}
+        {{ {entity_name}SortableField.{default_sort_field}, SortOrder.{default_sort_order} }}
     }};
 }}
 "#
-    );
+    )
 }
 
-pub fn new_repo_interface_name(entity_name: String) -> String {
-    return format!(
+pub fn new_repo_interface_name(entity_name: String, namespace: Option<String>, config: &CrudProjectConfig) -> String {
+    let namespace = config.repository_interface.namespace_declaration(namespace.as_deref());
+    let base = config.repository_interface.base_clause(&entity_name);
+    format!(
         r#"
-public interface I{entity_name}Repository :
-    ICrudRepository<{entity_name}>, IQueryCriteriaRepository<{entity_name}, {entity_name}QueryCriteria, {entity_name}SortableField>
+{namespace}
+
+public interface I{entity_name}Repository{base}
 {{
         
 }}
 "#
-    );
+    )
 }
 
 pub fn print_single_file(
@@ -102,24 +99,29 @@ pub fn print_single_file(
     query_criteria_class: String,
     new_repo: String,
     new_interface: String,
+    config: &CrudProjectConfig,
 ) -> String {
-    return format!(
+    let sortable_enum_path = config.sortable_field.path(&format!("{entity_name}SortableField.cs"));
+    let query_criteria_path = config.query_criteria.path(&format!("{entity_name}QueryCriteria.cs"));
+    let repo_path = config.repository.path(&format!("{entity_name}Repository.cs"));
+    let interface_path = config.repository_interface.path(&format!("I{entity_name}Repository.cs"));
+    format!(
         r#"
     // SORTABLE FIELD ENUM
-    // touch {base_project_route}Core/Domain/{entity_name}SortableField.cs
+    // touch {base_project_route}{sortable_enum_path}
 {sortable_enum}
 
     // QUERY CRITERIA CLASS
-    // touch {base_project_route}Core/Domain/{entity_name}QueryCriteria.cs
+    // touch {base_project_route}{query_criteria_path}
 {query_criteria_class}
 
     // Updated Repository
-    // touch {base_project_route}Infrastructure/Repositories/{entity_name}Repository
+    // touch {base_project_route}{repo_path}
 {new_repo}
 
     //UPDATED INTERFACE
-    // touch {base_project_route}Infrastructure/Interfaces/I{entity_name}Repository
+    // touch {base_project_route}{interface_path}
 {new_interface}
     "#,
-    );
+    )
 }
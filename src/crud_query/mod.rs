@@ -1 +1,6 @@
+pub mod filters;
 pub mod methods;
+pub mod patch;
+pub mod project_config;
+pub mod project_files;
+pub mod sorting;
@@ -0,0 +1,337 @@
+use std::{fs, io, ops::Range, path::Path};
+
+use crate::{
+    crud_query::{
+        filters::apply_criteria,
+        project_config::{ArtifactLayout, CrudProjectConfig},
+        project_files::ProjectFile,
+    },
+    utils::{
+        csharp_parser::parse_csharp,
+        csharp_syntax::{CsFile, TypeDecl, TypeKind},
+    },
+    FieldWithType,
+};
+
+/// A replacement of a byte range of the source.
+type Edit = (Range<usize>, String);
+
+/// Updates an existing repository in place: its base list gets the configured base
+/// class and interfaces, and `ApplyCriteria` is added or replaced. Usings and other
+/// members are left as they are, and patching an up-to-date file changes nothing.
+pub fn patch_repository(
+    source: &str,
+    entity_name: &str,
+    fields: Option<&[FieldWithType]>,
+    config: &CrudProjectConfig,
+) -> Result<String, String> {
+    let file = parse_csharp(source)?;
+    let decl = find_type(&file, &format!("{entity_name}Repository"), TypeKind::Class)
+        .ok_or("no repository class found")?;
+
+    let mut edits: Vec<Edit> = Vec::new();
+    edits.extend(base_list_edit(
+        source,
+        decl,
+        entity_name,
+        &config.repository,
+        true,
+    ));
+    if let Some(fields) = fields {
+        let method = apply_criteria(entity_name, fields, &config.filters);
+        match decl.methods().find(|m| m.name == "ApplyCriteria") {
+            Some(existing) if normalized(&source[existing.span.clone()]) != normalized(&method) => {
+                edits.push((existing.span.clone(), method.trim().to_string()));
+            }
+            Some(_) => {}
+            None => {
+                let body = decl
+                    .body_span
+                    .clone()
+                    .ok_or("repository class has no closing brace")?;
+                edits.push(insert_before_closing_brace(source, body.end - 1, &method));
+            }
+        }
+    }
+    Ok(apply_edits(source, edits))
+}
+
+/// Updates an existing repository interface in place so it extends the configured
+/// interfaces, keeping the ones it already extends.
+pub fn patch_interface(
+    source: &str,
+    entity_name: &str,
+    config: &CrudProjectConfig,
+) -> Result<String, String> {
+    let file = parse_csharp(source)?;
+    let decl = find_type(
+        &file,
+        &format!("I{entity_name}Repository"),
+        TypeKind::Interface,
+    )
+    .ok_or("no repository interface found")?;
+    let edits = base_list_edit(
+        source,
+        decl,
+        entity_name,
+        &config.repository_interface,
+        false,
+    );
+    Ok(apply_edits(source, edits.into_iter().collect()))
+}
+
+/// Replaces the generated repository and interface with patched copies of the files
+/// already at their place in the project, so their other members survive.
+pub fn patch_project_files(
+    root: &Path,
+    files: &mut [ProjectFile],
+    entity_name: &str,
+    fields: Option<&[FieldWithType]>,
+    config: &CrudProjectConfig,
+) -> Result<(), String> {
+    let repository = config
+        .repository
+        .path(&format!("{entity_name}Repository.cs"));
+    let interface = config
+        .repository_interface
+        .path(&format!("I{entity_name}Repository.cs"));
+    for file in files.iter_mut() {
+        let path = root.join(&file.path);
+        let existing = match fs::read_to_string(&path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        let patched = if file.path == Path::new(&repository) {
+            patch_repository(&existing, entity_name, fields, config)
+        } else if file.path == Path::new(&interface) {
+            patch_interface(&existing, entity_name, config)
+        } else {
+            continue;
+        };
+        file.content = patched.map_err(|e| format!("{}: {e}", path.display()))?;
+        file.patched = true;
+    }
+    Ok(())
+}
+
+/// The type with the given name, otherwise the first public one of that kind.
+fn find_type<'a>(file: &'a CsFile, name: &str, kind: TypeKind) -> Option<&'a TypeDecl> {
+    let types = file.all_types();
+    types
+        .iter()
+        .find(|decl| decl.kind == kind && decl.name == name)
+        .or_else(|| {
+            types
+                .iter()
+                .find(|decl| decl.kind == kind && decl.is_public())
+        })
+        .copied()
+}
+
+/// The configured base types first, then the existing ones not among them. A class's
+/// existing base class is dropped when `replace_base_class` is set, since it can only
+/// have one. `None` when nothing changes or no bases are configured.
+fn base_list_edit(
+    source: &str,
+    decl: &TypeDecl,
+    entity_name: &str,
+    layout: &ArtifactLayout,
+    replace_base_class: bool,
+) -> Option<Edit> {
+    let configured = layout.base_clause(entity_name);
+    let configured = split_bases(configured.strip_prefix(" : ")?);
+    let mut bases = configured.clone();
+    for existing in &decl.base_types {
+        let existing = existing.to_string();
+        let known = configured
+            .iter()
+            .any(|base| normalized(base) == normalized(&existing));
+        if !known && (!replace_base_class || is_interface_name(&existing)) {
+            bases.push(existing);
+        }
+    }
+
+    let span = decl.base_list_span.clone();
+    let current = source[span.clone()].trim_start_matches(':');
+    // a primary constructor's arguments to the base class aren't in `base_types`;
+    // the base class kept gets them, or the class stops compiling
+    let arguments = split_bases(current)
+        .iter()
+        .find_map(|base| constructor_arguments(base));
+    let base_class = bases.iter_mut().find(|base| !is_interface_name(base));
+    if let (Some(arguments), Some(base_class)) = (arguments, base_class) {
+        base_class.push_str(&arguments);
+    }
+    if normalized(current) == normalized(&bases.join(",")) {
+        return None;
+    }
+    let list = match span.is_empty() {
+        true => format!(" : {}", bases.join(", ")),
+        false => format!(": {}", bases.join(", ")),
+    };
+    Some((span, list))
+}
+
+/// Inserts `text` on its own lines before the `}` at `close`.
+fn insert_before_closing_brace(source: &str, close: usize, text: &str) -> Edit {
+    let line_start = source[..close].rfind('\n').map_or(0, |i| i + 1);
+    match source[line_start..close].trim().is_empty() {
+        true => (line_start..line_start, format!("{}\n", text.trim_end())),
+        false => (close..close, format!("{}\n", text.trim_end())),
+    }
+}
+
+fn apply_edits(source: &str, mut edits: Vec<Edit>) -> String {
+    let mut result = source.to_string();
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    for (range, text) in edits {
+        result.replace_range(range, &text);
+    }
+    result
+}
+
+/// Splits a base list at its top-level commas.
+fn split_bases(bases: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (index, c) in bases.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(bases[start..index].trim().to_string());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(bases[start..].trim().to_string());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Interfaces are told from classes by the `I` prefix convention: `ICustomerRepository`.
+/// The arguments passed to a base class in a base list: `(context)` in `Base<T>(context)`.
+fn constructor_arguments(base: &str) -> Option<String> {
+    let mut depth = 0;
+    for (index, c) in base.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            '(' if depth == 0 => return Some(base[index..].to_string()),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_interface_name(ty: &str) -> bool {
+    let name = ty.split('<').next().unwrap_or(ty);
+    let mut chars = name.rsplit('.').next().unwrap_or(name).chars();
+    chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn normalized(text: &str) -> String {
+    text.split_whitespace().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Vec<FieldWithType> {
+        vec![FieldWithType {
+            field: "Name".to_string(),
+            field_type: "string".to_string(),
+        }]
+    }
+
+    const REPOSITORY: &str = r#"using Acme.Core;
+
+namespace Acme.Infrastructure.Repositories;
+
+internal class CustomerRepository : BaseCrudRepository<Customer>, ICustomerRepository, IDisposable
+{
+    public CustomerRepository(CgwContext context) : base(context)
+    {
+    }
+
+    public void Dispose() { }
+}
+"#;
+
+    #[test]
+    fn test_repository_is_patched_once() {
+        let config = CrudProjectConfig::default();
+        let patched = patch_repository(REPOSITORY, "Customer", Some(&fields()), &config).unwrap();
+        assert!(patched.starts_with("using Acme.Core;"));
+        assert!(patched.contains(
+            "internal class CustomerRepository : BaseQueryCrudRepository<Customer, CustomerQueryCriteria, CustomerSortableField>, ICustomerRepository, IDisposable\n{"
+        ));
+        assert!(patched.contains("public void Dispose() { }\n\n    protected override IQueryable<Customer> ApplyCriteria("));
+        assert!(patched.ends_with("e => e.Name == criteria.Name);\n    }\n}\n"));
+        assert_eq!(
+            patch_repository(&patched, "Customer", Some(&fields()), &config).unwrap(),
+            patched
+        );
+    }
+
+    #[test]
+    fn test_primary_constructor_arguments_are_kept() {
+        let source = "public class CustomerRepository(CgwContext context) : BaseCrudRepository<Customer>(context), ICustomerRepository\n{\n}\n";
+        let config = CrudProjectConfig::default();
+        let patched = patch_repository(source, "Customer", Some(&fields()), &config).unwrap();
+        assert!(patched.starts_with(
+            "public class CustomerRepository(CgwContext context) : BaseQueryCrudRepository<Customer, CustomerQueryCriteria, CustomerSortableField>(context), ICustomerRepository\n{"
+        ));
+        assert_eq!(
+            patch_repository(&patched, "Customer", Some(&fields()), &config).unwrap(),
+            patched
+        );
+    }
+
+    #[test]
+    fn test_apply_criteria_is_replaced() {
+        let config = CrudProjectConfig::default();
+        let patched = patch_repository(REPOSITORY, "Customer", Some(&fields()), &config).unwrap();
+        let mut more = fields();
+        more.push(FieldWithType {
+            field: "Age".to_string(),
+            field_type: "int".to_string(),
+        });
+        let updated = patch_repository(&patched, "Customer", Some(&more), &config).unwrap();
+        assert_eq!(updated.matches("ApplyCriteria(").count(), 1);
+        assert!(updated.contains(".WhereIf(criteria.Age.HasValue, e => e.Age == criteria.Age);"));
+    }
+
+    #[test]
+    fn test_interface_keeps_its_members_and_bases() {
+        let source = r#"namespace Acme.Infrastructure.Interfaces;
+
+public interface ICustomerRepository : ICrudRepository<Customer>, IExportable
+{
+    Task<int> CountActiveAsync();
+}
+"#;
+        let config = CrudProjectConfig::default();
+        let patched = patch_interface(source, "Customer", &config).unwrap();
+        assert!(patched.contains(
+            "public interface ICustomerRepository : ICrudRepository<Customer>, IQueryCriteriaRepository<Customer, CustomerQueryCriteria, CustomerSortableField>, IExportable\n{\n    Task<int> CountActiveAsync();"
+        ));
+        assert_eq!(
+            patch_interface(&patched, "Customer", &config).unwrap(),
+            patched
+        );
+
+        let bare = patch_interface(
+            "public interface ICustomerRepository\n{\n}\n",
+            "Customer",
+            &config,
+        )
+        .unwrap();
+        assert!(
+            bare.starts_with("public interface ICustomerRepository : ICrudRepository<Customer>, ")
+        );
+    }
+}
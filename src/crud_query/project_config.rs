@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::crud_query::filters::FilterMode;
+
+/// Shape of a CRUD project config YAML file. Everything is optional and falls back
+/// to the defaults of [`CrudProjectConfig`]:
+///
+/// ```yaml
+/// db_context: SalesDbContext
+/// query_criteria:
+///   namespace: Application.Queries
+///   folder: src/Application/Queries
+/// repository:
+///   base: EfQueryRepository<{entity}, {entity}QueryCriteria, {entity}SortableField>
+/// filters:
+///   CreatedAt: range
+///   LastName: starts-with
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectConfigFile {
+    db_context: Option<String>,
+    sortable_field: ArtifactFile,
+    query_criteria: ArtifactFile,
+    repository: ArtifactFile,
+    repository_interface: ArtifactFile,
+    api: ArtifactFile,
+    repository_methods: RepositoryMethods,
+    filters: HashMap<String, FilterMode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ArtifactFile {
+    namespace: Option<String>,
+    folder: Option<String>,
+    base: Option<String>,
+}
+
+/// Names of the `I{Entity}Repository` methods the generated API endpoints call.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepositoryMethods {
+    /// Takes the query criteria and returns a page of entities.
+    pub query: String,
+    /// Takes an id and returns the entity or null.
+    pub get: String,
+    pub add: String,
+    pub update: String,
+    /// Takes the entity to delete.
+    pub delete: String,
+}
+
+impl Default for RepositoryMethods {
+    fn default() -> Self {
+        Self {
+            query: "GetPagedAsync".to_string(),
+            get: "GetByIdAsync".to_string(),
+            add: "AddAsync".to_string(),
+            update: "UpdateAsync".to_string(),
+            delete: "DeleteAsync".to_string(),
+        }
+    }
+}
+
+/// Where one generated type lives in the solution and what it derives from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArtifactLayout {
+    /// Namespace below the base namespace, e.g. `Core.Domain`.
+    pub namespace: String,
+    /// Folder below `BASE_PROJECT_ROUTE`, e.g. `Core/Domain`.
+    pub folder: String,
+    /// Base class, interfaces or enum underlying type. `{entity}` is replaced by the entity name.
+    pub base: Option<String>,
+}
+
+impl ArtifactLayout {
+    fn new(namespace: &str, folder: &str, base: Option<&str>) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            folder: folder.to_string(),
+            base: base.map(str::to_string),
+        }
+    }
+
+    fn merge(mut self, file: ArtifactFile) -> Self {
+        self.namespace = file.namespace.unwrap_or(self.namespace);
+        self.folder = file.folder.unwrap_or(self.folder);
+        self.base = file.base.or(self.base);
+        self
+    }
+
+    /// `namespace {base_namespace}.{namespace};`, or nothing without a base namespace.
+    pub fn namespace_declaration(&self, base_namespace: Option<&str>) -> String {
+        match (base_namespace, self.namespace.as_str()) {
+            (None, _) => "".to_string(),
+            (Some(base_namespace), "") => format!("namespace {base_namespace};"),
+            (Some(base_namespace), namespace) => format!("namespace {base_namespace}.{namespace};"),
+        }
+    }
+
+    /// ` : {base}` for the entity, or nothing when there is no base.
+    pub fn base_clause(&self, entity_name: &str) -> String {
+        match &self.base {
+            Some(base) => format!(" : {}", base.replace("{entity}", entity_name)),
+            None => "".to_string(),
+        }
+    }
+
+    /// `{folder}/{file_name}`.
+    pub fn path(&self, file_name: &str) -> String {
+        match self.folder.trim_end_matches('/') {
+            "" => file_name.to_string(),
+            folder => format!("{folder}/{file_name}"),
+        }
+    }
+}
+
+/// Solution layout the CRUD programs generate for: the repository's DbContext and,
+/// per generated type, its namespace, folder and base types.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrudProjectConfig {
+    pub db_context: String,
+    pub sortable_field: ArtifactLayout,
+    pub query_criteria: ArtifactLayout,
+    pub repository: ArtifactLayout,
+    pub repository_interface: ArtifactLayout,
+    /// The API controller or minimal-API endpoints; the base only applies to controllers.
+    pub api: ArtifactLayout,
+    pub repository_methods: RepositoryMethods,
+    /// Filter mode by field name; fields not listed are `Equals`.
+    pub filters: HashMap<String, FilterMode>,
+}
+
+impl Default for CrudProjectConfig {
+    fn default() -> Self {
+        Self {
+            db_context: "CgwContext".to_string(),
+            sortable_field: ArtifactLayout::new("Core.Domain", "Core/Domain", None),
+            query_criteria: ArtifactLayout::new(
+                "Core.Domain",
+                "Core/Domain",
+                Some("DefaultQueryCriteria<{entity}SortableField>"),
+            ),
+            repository: ArtifactLayout::new(
+                "Infrastructure.Repositories",
+                "Infrastructure/Repositories",
+                Some("BaseQueryCrudRepository<{entity}, {entity}QueryCriteria, {entity}SortableField>, I{entity}Repository"),
+            ),
+            repository_interface: ArtifactLayout::new(
+                "Infrastructure.Interfaces",
+                "Infrastructure/Interfaces",
+                Some("ICrudRepository<{entity}>, IQueryCriteriaRepository<{entity}, {entity}QueryCriteria, {entity}SortableField>"),
+            ),
+            api: ArtifactLayout::new("Api.Controllers", "Api/Controllers", Some("ControllerBase")),
+            repository_methods: RepositoryMethods::default(),
+            filters: HashMap::new(),
+        }
+    }
+}
+
+impl CrudProjectConfig {
+    /// Parses a project config file and merges it over the defaults.
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let file: ProjectConfigFile =
+            serde_yaml::from_str(yaml).map_err(|e| format!("invalid project config: {e}"))?;
+        let config = Self::default();
+        Ok(Self {
+            db_context: file.db_context.unwrap_or(config.db_context),
+            sortable_field: config.sortable_field.merge(file.sortable_field),
+            query_criteria: config.query_criteria.merge(file.query_criteria),
+            repository: config.repository.merge(file.repository),
+            repository_interface: config.repository_interface.merge(file.repository_interface),
+            api: config.api.merge(file.api),
+            repository_methods: file.repository_methods,
+            filters: file.filters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_overrides_defaults() {
+        let config = CrudProjectConfig::from_yaml(
+            r#"
+db_context: SalesDbContext
+query_criteria:
+  namespace: Application.Queries
+  folder: src/Application/Queries/
+repository:
+  base: EfRepository<{entity}>
+repository_methods:
+  query: QueryAsync
+filters:
+  CreatedAt: range
+  LastName: starts-with
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.db_context, "SalesDbContext");
+        assert_eq!(
+            config.query_criteria.namespace_declaration(Some("Acme")),
+            "namespace Acme.Application.Queries;"
+        );
+        assert_eq!(
+            config.query_criteria.path("CustomerQueryCriteria.cs"),
+            "src/Application/Queries/CustomerQueryCriteria.cs"
+        );
+        assert_eq!(
+            config.query_criteria.base_clause("Customer"),
+            " : DefaultQueryCriteria<CustomerSortableField>"
+        );
+        assert_eq!(
+            config.repository.base_clause("Customer"),
+            " : EfRepository<Customer>"
+        );
+        assert_eq!(config.repository.folder, "Infrastructure/Repositories");
+        assert_eq!(config.sortable_field.base_clause("Customer"), "");
+        assert_eq!(config.filters["LastName"], FilterMode::StartsWith);
+        assert_eq!(config.repository_methods.query, "QueryAsync");
+        assert_eq!(config.repository_methods.get, "GetByIdAsync");
+        assert_eq!(
+            CrudProjectConfig::from_yaml("").unwrap(),
+            CrudProjectConfig::default()
+        );
+        assert!(CrudProjectConfig::from_yaml("dbcontext: X").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::crud_query::project_config::CrudProjectConfig;
+
+/// What happened to a generated file when it was written into the project.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WriteOutcome {
+    Created,
+    /// Patched in place, or overwritten with `--force`.
+    Updated,
+    /// Already there and not forced.
+    Skipped,
+    /// Already there with the same content.
+    Unchanged,
+}
+
+/// A file to write into the project, relative to its root.
+#[derive(Clone, Debug)]
+pub struct ProjectFile {
+    pub path: PathBuf,
+    pub content: String,
+    /// The existing file edited in place, which is written without `--force`.
+    pub patched: bool,
+}
+
+impl fmt::Display for WriteOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            WriteOutcome::Created => "created",
+            WriteOutcome::Updated => "updated",
+            WriteOutcome::Skipped => "skipped",
+            WriteOutcome::Unchanged => "unchanged",
+        })
+    }
+}
+
+/// The files making up a query criteria CRUD, relative to the project root.
+pub fn crud_files(
+    entity_name: &str,
+    sortable_enum: String,
+    query_criteria_class: String,
+    new_repo: String,
+    new_interface: String,
+    config: &CrudProjectConfig,
+) -> Vec<ProjectFile> {
+    vec![
+        (
+            config
+                .sortable_field
+                .path(&format!("{entity_name}SortableField.cs")),
+            sortable_enum,
+        ),
+        (
+            config
+                .query_criteria
+                .path(&format!("{entity_name}QueryCriteria.cs")),
+            query_criteria_class,
+        ),
+        (
+            config
+                .repository
+                .path(&format!("{entity_name}Repository.cs")),
+            new_repo,
+        ),
+        (
+            config
+                .repository_interface
+                .path(&format!("I{entity_name}Repository.cs")),
+            new_interface,
+        ),
+    ]
+    .into_iter()
+    .map(|(path, content)| ProjectFile {
+        path: PathBuf::from(path),
+        content,
+        patched: false,
+    })
+    .collect()
+}
+
+/// Writes `files` below `root`, creating folders as needed. Existing files are only
+/// overwritten when their content differs, and they were patched or `force` is set.
+/// The files refer to each other, so nothing is written when an existing file can't
+/// be read, or when one would be skipped while another changes.
+pub fn write_project_files(
+    root: &Path,
+    files: &[ProjectFile],
+    force: bool,
+) -> Result<Vec<(PathBuf, WriteOutcome)>, String> {
+    let mut outcomes = Vec::new();
+    for file in files {
+        let path = root.join(&file.path);
+        let outcome = match fs::read_to_string(&path) {
+            Ok(existing) if existing == file.content => WriteOutcome::Unchanged,
+            Ok(_) if !(force || file.patched) => WriteOutcome::Skipped,
+            Ok(_) => WriteOutcome::Updated,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => WriteOutcome::Created,
+            // unreadable, e.g. not UTF-8 or no permission: it exists, so never clobber it
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        outcomes.push((path, outcome));
+    }
+    let skipped: Vec<String> = outcomes
+        .iter()
+        .filter(|(_, outcome)| *outcome == WriteOutcome::Skipped)
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    let changed = outcomes
+        .iter()
+        .any(|(_, outcome)| matches!(outcome, WriteOutcome::Created | WriteOutcome::Updated));
+    if !skipped.is_empty() && changed {
+        return Err(format!(
+            "{} already exist with other content and the other files depend on them; \
+             pass --force to overwrite them. Nothing was written",
+            skipped.join(", ")
+        ));
+    }
+    for (file, (path, outcome)) in files.iter().zip(&outcomes) {
+        if matches!(outcome, WriteOutcome::Created | WriteOutcome::Updated) {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+            }
+            fs::write(path, &file.content).map_err(|e| format!("{}: {e}", path.display()))?;
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Prints one line per file, and how to overwrite the skipped ones.
+pub fn print_summary(outcomes: &[(PathBuf, WriteOutcome)], force: bool) {
+    for (path, outcome) in outcomes {
+        println!("{outcome:>9} {}", path.display());
+    }
+    if !force
+        && outcomes
+            .iter()
+            .any(|(_, outcome)| *outcome == WriteOutcome::Skipped)
+    {
+        println!("skipped files already exist; pass --force to overwrite them");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_existing_files_are_only_overwritten_when_forced() {
+        let root = std::env::temp_dir().join(format!("crud-files-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let files = crud_files(
+            "Customer",
+            "enum".to_string(),
+            "criteria".to_string(),
+            "repo".to_string(),
+            "interface".to_string(),
+            &CrudProjectConfig::default(),
+        );
+
+        let outcomes = write_project_files(&root, &files, false).unwrap();
+        assert!(outcomes.iter().all(|(_, o)| *o == WriteOutcome::Created));
+        assert_eq!(
+            fs::read_to_string(root.join("Infrastructure/Interfaces/ICustomerRepository.cs"))
+                .unwrap(),
+            "interface"
+        );
+
+        let mut changed = files.clone();
+        changed[0].content = "new enum".to_string();
+        let outcomes = write_project_files(&root, &changed, false).unwrap();
+        assert_eq!(outcomes[0].1, WriteOutcome::Skipped);
+        assert!(outcomes[1..]
+            .iter()
+            .all(|(_, o)| *o == WriteOutcome::Unchanged));
+        assert_eq!(
+            fs::read_to_string(root.join("Core/Domain/CustomerSortableField.cs")).unwrap(),
+            "enum"
+        );
+
+        let outcomes: Vec<WriteOutcome> = write_project_files(&root, &changed, true)
+            .unwrap()
+            .into_iter()
+            .map(|(_, o)| o)
+            .collect();
+        assert_eq!(outcomes[0], WriteOutcome::Updated);
+        assert_eq!(outcomes[1], WriteOutcome::Unchanged);
+
+        changed[1].content = "patched criteria".to_string();
+        changed[1].patched = true;
+        let outcomes = write_project_files(&root, &changed, false).unwrap();
+        assert_eq!(outcomes[1].1, WriteOutcome::Updated);
+        assert_eq!(
+            fs::read_to_string(root.join("Core/Domain/CustomerSortableField.cs")).unwrap(),
+            "new enum"
+        );
+
+        // a patched repository can't be written against an out of date enum
+        let mut stale = changed.clone();
+        stale[0].content = "enum with Age".to_string();
+        stale[2].content = "repo filtering on Age".to_string();
+        stale[2].patched = true;
+        let error = write_project_files(&root, &stale, false).unwrap_err();
+        assert!(error.contains("CustomerSortableField.cs"), "{error}");
+        assert_eq!(
+            fs::read_to_string(root.join("Infrastructure/Repositories/CustomerRepository.cs"))
+                .unwrap(),
+            "repo"
+        );
+        let outcomes = write_project_files(&root, &stale, true).unwrap();
+        assert_eq!(outcomes[0].1, WriteOutcome::Updated);
+        assert_eq!(outcomes[2].1, WriteOutcome::Updated);
+
+        fs::write(
+            root.join("Core/Domain/CustomerSortableField.cs"),
+            [0xff, 0xfe],
+        )
+        .unwrap();
+        changed[2].content = "new repo".to_string();
+        assert!(write_project_files(&root, &changed, true).is_err());
+        assert_eq!(
+            fs::read(root.join("Core/Domain/CustomerSortableField.cs")).unwrap(),
+            [0xff, 0xfe]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("Infrastructure/Repositories/CustomerRepository.cs"))
+                .unwrap(),
+            "repo filtering on Age"
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
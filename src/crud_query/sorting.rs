@@ -0,0 +1,161 @@
+use std::{fmt, str::FromStr};
+
+use crate::FieldWithType;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SortOrder::Ascending => "Ascending",
+            SortOrder::Descending => "Descending",
+        })
+    }
+}
+
+/// The criteria's default `SortCriteria` as given on the command line, IE: `CreatedAt:desc`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefaultSort {
+    pub field: String,
+    pub order: SortOrder,
+}
+
+impl FromStr for DefaultSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, order) = s.split_once(':').unwrap_or((s, "asc"));
+        let order = match order.trim().to_lowercase().as_str() {
+            "asc" | "ascending" => SortOrder::Ascending,
+            "desc" | "descending" => SortOrder::Descending,
+            _ => return Err(format!("expected `field` or `field:asc|desc`, found `{s}`")),
+        };
+        let field = field.trim();
+        if field.is_empty() {
+            return Err(format!("expected `field` or `field:asc|desc`, found `{s}`"));
+        }
+        Ok(Self {
+            field: field.to_string(),
+            order,
+        })
+    }
+}
+
+/// Which fields the sortable enum lists and which one the criteria sort by by default.
+#[derive(Clone, Debug, Default)]
+pub struct SortOptions {
+    /// Members of the sortable enum; when empty, the id followed by every field.
+    pub fields: Vec<String>,
+    /// Defaults to the id, ascending.
+    pub default: Option<DefaultSort>,
+}
+
+/// Members of the sortable enum, without duplicates.
+pub fn sortable_members(
+    entity_id_name: &str,
+    fields: Option<&[FieldWithType]>,
+    sort: &SortOptions,
+) -> Vec<String> {
+    let members: Vec<&str> = match sort.fields.as_slice() {
+        [] => std::iter::once(entity_id_name)
+            .chain(fields.unwrap_or_default().iter().map(|f| f.field.as_str()))
+            .collect(),
+        fields => fields.iter().map(String::as_str).collect(),
+    };
+    let mut unique: Vec<String> = Vec::new();
+    for member in members {
+        if !unique.iter().any(|m| m == member) {
+            unique.push(member.to_string());
+        }
+    }
+    unique
+}
+
+/// The field and order the criteria sort by unless told otherwise.
+pub fn default_sort(entity_id_name: &str, sort: &SortOptions) -> DefaultSort {
+    sort.default.clone().unwrap_or(DefaultSort {
+        field: entity_id_name.to_string(),
+        order: SortOrder::Ascending,
+    })
+}
+
+/// Fails when the default sort field isn't a member of the sortable enum.
+pub fn check_sort(
+    entity_id_name: &str,
+    fields: Option<&[FieldWithType]>,
+    sort: &SortOptions,
+) -> Result<(), String> {
+    let default = default_sort(entity_id_name, sort);
+    match sortable_members(entity_id_name, fields, sort).contains(&default.field) {
+        true => Ok(()),
+        false => Err(format!(
+            "default sort field {} is not one of the sortable fields",
+            default.field
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(names: &[&str]) -> Vec<FieldWithType> {
+        names
+            .iter()
+            .map(|name| FieldWithType {
+                field: name.to_string(),
+                field_type: "string".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_members_come_from_the_fields() {
+        let fields = fields(&["Name", "CustomerId", "EffectiveDate"]);
+        assert_eq!(
+            sortable_members("CustomerId", Some(&fields), &SortOptions::default()),
+            vec!["CustomerId", "Name", "EffectiveDate"]
+        );
+        assert_eq!(
+            sortable_members("CustomerId", None, &SortOptions::default()),
+            vec!["CustomerId"]
+        );
+        let explicit = SortOptions {
+            fields: vec!["Name".to_string(), "CreatedAt".to_string()],
+            default: None,
+        };
+        assert_eq!(
+            sortable_members("CustomerId", Some(&fields), &explicit),
+            vec!["Name", "CreatedAt"]
+        );
+        assert!(check_sort("CustomerId", Some(&fields), &explicit).is_err());
+    }
+
+    #[test]
+    fn test_default_sort() {
+        let sort = SortOptions {
+            fields: Vec::new(),
+            default: Some(DefaultSort::from_str("Name:desc").unwrap()),
+        };
+        assert_eq!(
+            default_sort("CustomerId", &sort),
+            DefaultSort {
+                field: "Name".to_string(),
+                order: SortOrder::Descending
+            }
+        );
+        assert!(check_sort("CustomerId", Some(&fields(&["Name"])), &sort).is_ok());
+        assert!(check_sort("CustomerId", None, &sort).is_err());
+        assert_eq!(
+            DefaultSort::from_str("Name").unwrap().order,
+            SortOrder::Ascending
+        );
+        assert!(DefaultSort::from_str("Name:sideways").is_err());
+        assert!(DefaultSort::from_str(":desc").is_err());
+    }
+}
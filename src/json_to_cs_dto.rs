@@ -0,0 +1,521 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+};
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{
+    ts_interface_to_cs_dto::{cs_string_literal, CsDtoStyle},
+    utils::json_naming::{pascal_case, NamingPolicy},
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct JsonDtoOptions {
+    /// Name of the class for the top-level object.
+    pub root_name: String,
+    pub style: CsDtoStyle,
+    /// Policy the API serializes property names with; names it wouldn't produce
+    /// get a `[JsonPropertyName]`.
+    pub naming_policy: NamingPolicy,
+    /// `BASE_NAMESPACE`; the DTOs go in its `Core.Dtos` namespace.
+    pub base_namespace: Option<String>,
+}
+
+/// Infers C# DTOs from sample JSON documents of the same payload. Each sample is an
+/// object, or an array whose items are samples. Merging samples decides which
+/// properties are optional (missing from some samples) and nullable (`null` in some).
+pub fn json_to_cs_dto(samples: &[String], options: &JsonDtoOptions) -> Result<String, String> {
+    let mut root = Shape::Unknown;
+    for (index, sample) in samples.iter().enumerate() {
+        let value: Value = serde_json::from_str(sample)
+            .map_err(|e| format!("sample {}: invalid JSON: {e}", index + 1))?;
+        let items = match value {
+            Value::Array(items) => items,
+            value => vec![value],
+        };
+        for item in &items {
+            root = root.merge(infer(item).shape);
+        }
+    }
+    let Shape::Object { fields, count } = root else {
+        return Err("expected JSON objects, or arrays of objects, as samples".to_string());
+    };
+
+    let generator = Generator {
+        options,
+        classes: RefCell::new(Vec::new()),
+        uses_json_attributes: Cell::new(false),
+    };
+    generator.class(&options.root_name, "", &fields, count);
+
+    let mut output = String::new();
+    if generator.uses_json_attributes.get() {
+        output.push_str("using System.Text.Json.Serialization;\n\n");
+    }
+    if let Some(base_namespace) = &options.base_namespace {
+        output.push_str(&format!("namespace {base_namespace}.Core.Dtos;\n\n"));
+    }
+    let classes: Vec<String> = generator
+        .classes
+        .into_inner()
+        .into_iter()
+        .map(|class| class.text)
+        .collect();
+    output.push_str(&classes.join("\n"));
+    Ok(output)
+}
+
+/// The type of the values seen at one place in the samples.
+#[derive(Clone, Debug, PartialEq)]
+enum Shape {
+    /// Only `null`, or the items of arrays that were always empty.
+    Unknown,
+    Bool,
+    /// `large` once a value doesn't fit an `int`.
+    Integer {
+        large: bool,
+    },
+    Number,
+    String(StringKind),
+    Array(Box<Typed>),
+    /// `count` is the number of objects merged, to tell optional fields apart.
+    Object {
+        fields: Vec<Field>,
+        count: usize,
+    },
+    /// Values of incompatible types.
+    Mixed,
+}
+
+/// Strings whose every sample has a recognizable format.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StringKind {
+    Plain,
+    Guid,
+    Date,
+    DateTime,
+    DateTimeOffset,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Typed {
+    shape: Shape,
+    /// `null` was seen.
+    nullable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    name: String,
+    ty: Typed,
+    /// Number of objects the field was present in.
+    occurrences: usize,
+}
+
+impl Shape {
+    fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Unknown, shape) | (shape, Shape::Unknown) => shape,
+            (Shape::Integer { large: a }, Shape::Integer { large: b }) => {
+                Shape::Integer { large: a || b }
+            }
+            (Shape::Integer { .. } | Shape::Number, Shape::Integer { .. } | Shape::Number) => {
+                Shape::Number
+            }
+            (Shape::String(a), Shape::String(b)) => Shape::String(match a == b {
+                true => a,
+                false => StringKind::Plain,
+            }),
+            (Shape::Array(a), Shape::Array(b)) => Shape::Array(Box::new(a.merge(*b))),
+            (
+                Shape::Object {
+                    fields: mut merged,
+                    count: a,
+                },
+                Shape::Object { fields, count: b },
+            ) => {
+                for field in fields {
+                    match merged.iter().position(|f| f.name == field.name) {
+                        Some(i) => {
+                            let existing = merged.remove(i);
+                            merged.insert(
+                                i,
+                                Field {
+                                    name: field.name,
+                                    ty: existing.ty.merge(field.ty),
+                                    occurrences: existing.occurrences + field.occurrences,
+                                },
+                            );
+                        }
+                        None => merged.push(field),
+                    }
+                }
+                Shape::Object {
+                    fields: merged,
+                    count: a + b,
+                }
+            }
+            (a, b) if a == b => a,
+            _ => Shape::Mixed,
+        }
+    }
+}
+
+impl Typed {
+    fn merge(self, other: Typed) -> Typed {
+        Typed {
+            shape: self.shape.merge(other.shape),
+            nullable: self.nullable || other.nullable,
+        }
+    }
+}
+
+fn infer(value: &Value) -> Typed {
+    let shape = match value {
+        Value::Null => {
+            return Typed {
+                shape: Shape::Unknown,
+                nullable: true,
+            }
+        }
+        Value::Bool(_) => Shape::Bool,
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Shape::Integer {
+                large: i32::try_from(integer).is_err(),
+            },
+            None if number.is_u64() => Shape::Integer { large: true },
+            None => Shape::Number,
+        },
+        Value::String(text) => Shape::String(string_kind(text)),
+        Value::Array(items) => {
+            let item = items.iter().map(infer).fold(
+                Typed {
+                    shape: Shape::Unknown,
+                    nullable: false,
+                },
+                Typed::merge,
+            );
+            Shape::Array(Box::new(item))
+        }
+        Value::Object(object) => Shape::Object {
+            fields: object
+                .iter()
+                .map(|(name, value)| Field {
+                    name: name.clone(),
+                    ty: infer(value),
+                    occurrences: 1,
+                })
+                .collect(),
+            count: 1,
+        },
+    };
+    Typed {
+        shape,
+        nullable: false,
+    }
+}
+
+fn string_kind(text: &str) -> StringKind {
+    let guid = Regex::new(r"^[0-9a-fA-F]{8}(-[0-9a-fA-F]{4}){3}-[0-9a-fA-F]{12}$").unwrap();
+    let date = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    let date_time =
+        Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}(:\d{2}(\.\d+)?)?(?<zone>Z|[+-]\d{2}:\d{2})?$")
+            .unwrap();
+    if guid.is_match(text) {
+        return StringKind::Guid;
+    }
+    if date.is_match(text) {
+        return StringKind::Date;
+    }
+    match date_time.captures(text) {
+        Some(captures) if captures.name("zone").is_some() => StringKind::DateTimeOffset,
+        Some(_) => StringKind::DateTime,
+        None => StringKind::Plain,
+    }
+}
+
+struct Class {
+    name: String,
+    fields: Vec<Field>,
+    text: String,
+}
+
+struct Generator<'a> {
+    options: &'a JsonDtoOptions,
+    /// Classes in output order; the root first.
+    classes: RefCell<Vec<Class>>,
+    uses_json_attributes: Cell<bool>,
+}
+
+impl Generator<'_> {
+    /// Emits a class for an object shape and returns its name. Objects of the same
+    /// shape share a class; a name already taken by another shape is prefixed with
+    /// the parent class's name.
+    fn class(&self, name: &str, parent: &str, fields: &[Field], count: usize) -> String {
+        let mut candidates = vec![name.to_string(), format!("{parent}{name}")];
+        candidates.extend((2..).map(|n| format!("{parent}{name}{n}")).take(100));
+        let mut class_name = name.to_string();
+        for candidate in candidates {
+            let classes = self.classes.borrow();
+            match classes.iter().find(|class| class.name == candidate) {
+                Some(class) if class.fields == fields => return candidate,
+                Some(_) => continue,
+                None => {
+                    class_name = candidate;
+                    break;
+                }
+            }
+        }
+        // reserve the slot so classes of nested objects follow their owner
+        let slot = self.classes.borrow().len();
+        self.classes.borrow_mut().push(Class {
+            name: class_name.clone(),
+            fields: fields.to_vec(),
+            text: String::new(),
+        });
+
+        let keyword = match self.options.style {
+            CsDtoStyle::Class => "class",
+            CsDtoStyle::Record => "record",
+        };
+        let setter = match self.options.style {
+            CsDtoStyle::Class => "set",
+            CsDtoStyle::Record => "init",
+        };
+        let mut used_names = HashSet::from([class_name.clone()]);
+        let mut properties = Vec::new();
+        for field in fields {
+            let mut property_name = pascal_case(&field.name);
+            // a member can't share its class's name, nor another member's
+            if property_name == class_name {
+                property_name.push_str("Value");
+            }
+            let base_name = property_name.clone();
+            let mut n = 2;
+            while !used_names.insert(property_name.clone()) {
+                property_name = format!("{base_name}{n}");
+                n += 1;
+            }
+
+            let is_optional = field.occurrences < count;
+            let cs_type = self.cs_type(&field.ty.shape, &pascal_case(&field.name), &class_name);
+            let nullable_marker = match is_optional || field.ty.nullable {
+                true => "?",
+                false => "",
+            };
+            let required = match is_optional {
+                true => "",
+                false => "required ",
+            };
+            let mut property = String::new();
+            if self.options.naming_policy.apply(&property_name) != field.name {
+                self.uses_json_attributes.set(true);
+                property.push_str(&format!(
+                    "    [JsonPropertyName({})]\n",
+                    cs_string_literal(&field.name)
+                ));
+            }
+            property.push_str(&format!(
+                "    public {required}{cs_type}{nullable_marker} {property_name} {{ get; {setter}; }}\n"
+            ));
+            properties.push(property);
+        }
+
+        self.classes.borrow_mut()[slot].text = format!(
+            "public {keyword} {class_name}\n{{\n{}}}\n",
+            properties.join("\n")
+        );
+        class_name
+    }
+
+    /// The C# type of a shape. Objects become classes named after their property,
+    /// and items of arrays after the singular of it.
+    fn cs_type(&self, shape: &Shape, name: &str, parent: &str) -> String {
+        match shape {
+            Shape::Unknown | Shape::Mixed => "object".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer { large: false } => "int".to_string(),
+            Shape::Integer { large: true } => "long".to_string(),
+            Shape::Number => "double".to_string(),
+            Shape::String(kind) => match kind {
+                StringKind::Plain => "string",
+                StringKind::Guid => "Guid",
+                StringKind::Date => "DateOnly",
+                StringKind::DateTime => "DateTime",
+                StringKind::DateTimeOffset => "DateTimeOffset",
+            }
+            .to_string(),
+            Shape::Array(item) => {
+                let nullable_marker = if item.nullable { "?" } else { "" };
+                format!(
+                    "List<{}{nullable_marker}>",
+                    self.cs_type(&item.shape, &singular(name), parent)
+                )
+            }
+            Shape::Object { fields, .. } if fields.is_empty() => "object".to_string(),
+            Shape::Object { fields, count } => self.class(name, parent, fields, *count),
+        }
+    }
+}
+
+/// `Addresses` → `Address`, `Categories` → `Category`; names that don't look plural get `Item`.
+fn singular(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        return format!("{stem}y");
+    }
+    for suffix in ["sses", "xes", "ches", "shes"] {
+        if name.ends_with(suffix) {
+            return name[..name.len() - 2].to_string();
+        }
+    }
+    match name.strip_suffix('s') {
+        // `Status`, `Address`, `Analysis` aren't plurals
+        Some(stem) if !stem.is_empty() && !stem.ends_with(['s', 'u', 'i']) => stem.to_string(),
+        _ => format!("{name}Item"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> JsonDtoOptions {
+        JsonDtoOptions {
+            root_name: "OrderDto".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn convert(samples: &[&str], options: &JsonDtoOptions) -> String {
+        let samples: Vec<String> = samples.iter().map(|s| s.to_string()).collect();
+        json_to_cs_dto(&samples, options).unwrap()
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let cs = convert(
+            &[r#"{
+                "id": "5f0c6f3e-7a52-4c5e-9a1a-2b1f6d1e4c11",
+                "number": 42,
+                "total": 19.99,
+                "createdAt": "2024-05-01T10:00:00Z",
+                "dueDate": "2024-06-01",
+                "paid": false,
+                "customer": { "name": "Ann", "vip_level": 3 },
+                "lines": [{ "sku": "A-1", "qty": 2 }],
+                "tags": [],
+                "external_id": 9007199254740993
+            }"#],
+            &JsonDtoOptions {
+                base_namespace: Some("Acme".to_string()),
+                ..options()
+            },
+        );
+        assert_eq!(
+            cs,
+            r#"using System.Text.Json.Serialization;
+
+namespace Acme.Core.Dtos;
+
+public class OrderDto
+{
+    public required Guid Id { get; set; }
+
+    public required int Number { get; set; }
+
+    public required double Total { get; set; }
+
+    public required DateTimeOffset CreatedAt { get; set; }
+
+    public required DateOnly DueDate { get; set; }
+
+    public required bool Paid { get; set; }
+
+    public required Customer Customer { get; set; }
+
+    public required List<Line> Lines { get; set; }
+
+    public required List<object> Tags { get; set; }
+
+    [JsonPropertyName("external_id")]
+    public required long ExternalId { get; set; }
+}
+
+public class Customer
+{
+    public required string Name { get; set; }
+
+    [JsonPropertyName("vip_level")]
+    public required int VipLevel { get; set; }
+}
+
+public class Line
+{
+    public required string Sku { get; set; }
+
+    public required int Qty { get; set; }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_merged_samples_decide_optional_and_nullable() {
+        let cs = convert(
+            &[
+                r#"{ "id": 1, "note": null, "price": 1, "code": "x", "items": [1, null] }"#,
+                r#"[{ "id": 2, "note": "n", "price": 2.5, "code": 7, "coupon": "c", "items": [] }]"#,
+            ],
+            &options(),
+        );
+        assert!(cs.contains("public required int Id { get; set; }"));
+        assert!(cs.contains("public required string? Note { get; set; }"));
+        assert!(cs.contains("public required double Price { get; set; }"));
+        assert!(cs.contains("public required object Code { get; set; }"));
+        assert!(cs.contains("public required List<int?> Items { get; set; }"));
+        assert!(cs.contains("public string? Coupon { get; set; }"));
+    }
+
+    #[test]
+    fn test_classes_are_shared_or_prefixed() {
+        let cs = convert(
+            &[r#"{
+                "billing": { "address": { "street": "a" } },
+                "shipping": { "address": { "street": "b" } },
+                "store": { "address": { "zip": 1 } },
+                "categories": [{ "id": 1 }]
+            }"#],
+            &JsonDtoOptions {
+                style: CsDtoStyle::Record,
+                naming_policy: NamingPolicy::Camel,
+                ..options()
+            },
+        );
+        assert!(cs.contains("public record Address\n"));
+        assert!(cs.contains("public record StoreAddress\n"));
+        assert!(cs.contains("public required StoreAddress Address { get; init; }"));
+        assert!(cs.contains("public required List<Category> Categories { get; init; }"));
+        assert_eq!(cs.matches("public record Address\n").count(), 1);
+    }
+
+    #[test]
+    fn test_non_object_samples_are_errors() {
+        let samples = vec!["[1, 2]".to_string()];
+        assert!(json_to_cs_dto(&samples, &options()).is_err());
+        let samples = vec!["{ invalid".to_string()];
+        assert!(json_to_cs_dto(&samples, &options())
+            .unwrap_err()
+            .starts_with("sample 1: invalid JSON"));
+    }
+
+    #[test]
+    fn test_singular() {
+        assert_eq!(singular("Addresses"), "Address");
+        assert_eq!(singular("Categories"), "Category");
+        assert_eq!(singular("Lines"), "Line");
+        assert_eq!(singular("Status"), "StatusItem");
+        assert_eq!(singular("Data"), "DataItem");
+    }
+}
@@ -1,17 +1,36 @@
+use c_sharp_dto_to_json_schema::JsonSchemaOptions;
+use c_sharp_dto_to_ts_batch::TsLayout;
+use c_sharp_dto_to_ts_interface::{
+    EnumSerialization, EnumStyle, NullableContext, NullablePolicy, TsFormat, TsModule, TsOptions,
+};
 use clap::Parser;
+use crud_api_endpoints::{ApiOptions, ApiStyle};
+use crud_query::{
+    filters::FieldFilter,
+    project_config::CrudProjectConfig,
+    sorting::{DefaultSort, SortOptions},
+};
 use dotenv::dotenv;
 use itertools::Itertools;
+use json_to_cs_dto::JsonDtoOptions;
 use serde::Serialize;
 use std::fs::File;
 use std::io::{self, Error, Read, Write};
-use std::os::unix::process;
 use std::path::Path;
-use std::process::Output;
 use std::str::FromStr;
+use ts_interface_to_cs_dto::{CsDtoOptions, CsDtoStyle, CsNumberType};
+use utils::{
+    csharp_syntax::CsFile, entity_fields, json_naming::NamingPolicy, ts_type_map::TsTypeMap,
+};
 mod base_crud_from_entity;
 mod base_crud_to_query_crud;
+mod c_sharp_dto_to_json_schema;
+mod c_sharp_dto_to_ts_batch;
 mod c_sharp_dto_to_ts_interface;
+mod crud_api_endpoints;
 mod crud_query;
+mod json_to_cs_dto;
+mod ts_interface_to_cs_dto;
 mod unit_test_generator;
 
 mod utils;
@@ -20,8 +39,12 @@ mod utils;
 #[serde(rename_all = "kebab-case")]
 enum Programs {
     CsDtoToTsInterface,
+    CsDtoToJsonSchema,
+    GenerateApiEndpoints,
     GenerateQueryCriteriaFromBaseCrudClass,
     GenerateQueryCriterialFromEntityName,
+    JsonToCsDto,
+    TsInterfaceToCsDto,
     UnitTestGenerator,
 }
 
@@ -36,11 +59,13 @@ impl FromStr for FieldWithType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (field, field_type) = s
-            .split(",")
-            .collect::<Vec<_>>()
-            .into_iter()
+            .split(',')
+            .map(str::trim)
             .collect_tuple()
-            .unwrap();
+            .filter(|(field, field_type): &(&str, &str)| {
+                !field.is_empty() && !field_type.is_empty()
+            })
+            .ok_or(format!("expected `name,type`, found `{s}`"))?;
 
         Ok(Self {
             field: field.to_string(),
@@ -56,11 +81,11 @@ struct Args {
     #[arg(short, long)]
     program: Programs,
 
-    /// Input file
+    /// Input file. cs-dto-to-ts-interface also takes a directory or a glob such as `Dtos/**/*.cs`
     #[arg(short, long)]
     in_file: Option<String>,
 
-    /// Output file
+    /// Output file, or output directory when converting a directory or glob
     #[arg(short, long)]
     out_file: String,
 
@@ -68,6 +93,7 @@ struct Args {
     #[arg(short, long)]
     entity_id_name: Option<String>,
 
+    /// Namespace the generated code goes in; defaults to `BASE_NAMESPACE`
     #[arg(short, long)]
     base_name_space: Option<String>,
 
@@ -75,42 +101,264 @@ struct Args {
     #[clap(short, long, value_parser, num_args = 1.., value_delimiter = ' ')]
     type_sortable_fields: Option<Vec<FieldWithType>>,
 
-    /// name of the entity
+    /// CRUD programs: YAML file of the project's DbContext and the namespace, folder and base
+    /// types of each generated file
+    #[arg(long)]
+    project_config: Option<String>,
+
+    /// Filter mode per field, overriding the project config, IE: CreatedAt=range LastName=starts-with Id=in.
+    /// Modes: equals (default), range, contains, starts-with, in
+    #[arg(long, num_args = 1..)]
+    filter: Vec<FieldFilter>,
+
+    /// Members of the sortable enum; defaults to the id and every sort field
+    #[arg(long, num_args = 1..)]
+    sort_field: Vec<String>,
+
+    /// Default SortCriteria of the query criteria, IE: CreatedAt:desc. Defaults to the id, ascending
+    #[arg(long)]
+    default_sort: Option<DefaultSort>,
+
+    /// generate-api-endpoints: an `[ApiController]` class or a minimal-API route group
+    #[arg(long, value_enum, default_value_t)]
+    api_style: ApiStyle,
+
+    /// Overwrite CRUD files that already exist under `BASE_PROJECT_ROUTE`
+    #[arg(long)]
+    force: bool,
+
+    /// Entity class file; its scalar properties become the sort and filter fields, with any
+    /// `--type-sortable-fields` added
+    #[arg(long)]
+    entity_file: Option<String>,
+
+    /// Only use the entity properties matching one of these case-insensitive globs, IE: *Name Status
+    #[arg(long, num_args = 1..)]
+    include_field: Vec<String>,
+
+    /// Leave out the entity properties matching one of these case-insensitive globs
+    #[arg(long, num_args = 1..)]
+    exclude_field: Vec<String>,
+
+    /// name of the entity. json-to-cs-dto: name of the root class, defaults to the input file name
     #[arg(short = 'x', long)]
     entity_name: Option<String>,
+
+    /// Generate TypeScript interfaces or Zod schemas
+    #[arg(long, value_enum, default_value_t)]
+    ts_format: TsFormat,
+
+    /// How C# enums are emitted in TypeScript
+    #[arg(long, value_enum, default_value_t)]
+    enum_style: EnumStyle,
+
+    /// How the API serializes enums: numbers, or names via JsonStringEnumConverter
+    #[arg(long, value_enum, default_value_t)]
+    enum_serialization: EnumSerialization,
+
+    /// Property naming policy of the API's JSON serializer
+    #[arg(long, value_enum, default_value_t)]
+    naming_policy: NamingPolicy,
+
+    /// How properties that can be null are typed: `prop?: T`, `prop: T | null` or `prop: T`
+    #[arg(long, value_enum, default_value_t)]
+    nullable_policy: NullablePolicy,
+
+    /// Nullable annotation context of files without a `#nullable` directive (the project's `<Nullable>`)
+    #[arg(long, value_enum, default_value_t)]
+    nullable_context: NullableContext,
+
+    /// YAML file of C# to TypeScript type mappings, merged over the built-in defaults
+    #[arg(long)]
+    type_map: Option<String>,
+
+    /// Other DTO files whose types the input refers to. TypeScript imports them from
+    /// `./<file stem>`; JSON Schema adds the types used to `$defs`. With `--entity-file`:
+    /// files declaring the entity's base class and enums
+    #[arg(long, num_args = 1..)]
+    reference: Vec<String>,
+
+    /// When converting a directory or glob: one TypeScript file per type or per C# file
+    #[arg(long, value_enum, default_value_t)]
+    ts_layout: TsLayout,
+
+    /// Generate C# classes or records from TypeScript
+    #[arg(long, value_enum, default_value_t)]
+    cs_style: CsDtoStyle,
+
+    /// C# type that TypeScript's `number` maps to
+    #[arg(long, value_enum, default_value_t)]
+    number_type: CsNumberType,
+
+    /// More sample JSON documents of the same payload, merged with the input
+    #[arg(long, num_args = 1..)]
+    sample: Vec<String>,
+
+    /// json-to-cs-dto: also write the matching TypeScript interfaces to this file
+    #[arg(long)]
+    emit_ts: Option<String>,
 }
 
 fn main() -> io::Result<()> {
     dotenv().ok();
     let args = Args::parse();
     println!("{:?}", args.type_sortable_fields);
+    let sortable_fields = sortable_fields(&args);
+    let project_config = project_config(&args);
+
+    if let (Programs::CsDtoToTsInterface, Some(input)) = (&args.program, &args.in_file) {
+        if c_sharp_dto_to_ts_batch::is_batch_input(input) {
+            let written = c_sharp_dto_to_ts_batch::run(
+                input,
+                Path::new(&args.out_file),
+                args.ts_layout,
+                &ts_options(&args),
+            )
+            .expect("Error converting C# files");
+            for path in written {
+                println!("created {}", path.display());
+            }
+            return Ok(());
+        }
+    }
 
     // Process the input content based on the program type
     let output_content: String = match args.program {
         Programs::CsDtoToTsInterface => c_sharp_dto_to_ts_interface::csharp_dto_to_ts_interface(
-            read_from_file(args.in_file.expect("Input file must be specified").as_str())
-                .expect("Error reading file"),
-        ),
+            read_from_file(
+                args.in_file
+                    .as_deref()
+                    .expect("Input file must be specified"),
+            )
+            .expect("Error reading file"),
+            &args
+                .reference
+                .iter()
+                .map(|path| {
+                    TsModule::from_file(path, &read_from_file(path).expect("Error reading file"))
+                        .expect("Error parsing reference file")
+                })
+                .collect::<Vec<_>>(),
+            &ts_options(&args),
+        )
+        .expect("Error parsing C# file"),
+        Programs::CsDtoToJsonSchema => c_sharp_dto_to_json_schema::csharp_dto_to_json_schema(
+            read_from_file(
+                args.in_file
+                    .as_deref()
+                    .expect("Input file must be specified"),
+            )
+            .expect("Error reading file"),
+            &reference_files(&args),
+            &JsonSchemaOptions {
+                enum_serialization: args.enum_serialization,
+                naming_policy: args.naming_policy,
+                nullable_context: args.nullable_context,
+            },
+        )
+        .expect("Error parsing C# file"),
         Programs::GenerateQueryCriteriaFromBaseCrudClass => base_crud_to_query_crud::run(
             read_from_file(args.in_file.expect("Input file must be specified").as_str())
                 .expect("Error reading file"),
             args.entity_id_name,
-            args.type_sortable_fields,
-        ),
+            sortable_fields,
+            args.force,
+            &project_config,
+            &SortOptions {
+                fields: args.sort_field.clone(),
+                default: args.default_sort.clone(),
+            },
+        )
+        .map_err(|e| io::Error::other(format!("Error generating query criteria: {e}")))?,
         Programs::GenerateQueryCriterialFromEntityName => base_crud_from_entity::run(
             args.entity_name.expect("Entity Name field is required"),
             args.entity_id_name,
-            args.type_sortable_fields,
-        ),
+            sortable_fields,
+            args.force,
+            &project_config,
+            &SortOptions {
+                fields: args.sort_field.clone(),
+                default: args.default_sort.clone(),
+            },
+        )
+        .expect("Error generating query criteria"),
+        Programs::GenerateApiEndpoints => crud_api_endpoints::run(
+            args.entity_name
+                .clone()
+                .expect("Entity Name field is required"),
+            args.entity_id_name.clone(),
+            args.in_file
+                .as_deref()
+                .map(|dto| read_from_file(dto).expect("Error reading file")),
+            sortable_fields,
+            &ApiOptions {
+                style: args.api_style,
+                base_namespace: base_namespace(&args),
+            },
+            args.force,
+            &project_config,
+        )
+        .expect("Error generating API endpoints"),
+        Programs::TsInterfaceToCsDto => ts_interface_to_cs_dto::ts_interface_to_cs_dto(
+            read_from_file(
+                args.in_file
+                    .as_deref()
+                    .expect("Input file must be specified"),
+            )
+            .expect("Error reading file"),
+            &CsDtoOptions {
+                style: args.cs_style,
+                number_type: args.number_type,
+                naming_policy: args.naming_policy,
+                base_namespace: base_namespace(&args),
+            },
+        )
+        .expect("Error parsing TypeScript file"),
+        Programs::JsonToCsDto => {
+            let in_file = args
+                .in_file
+                .as_deref()
+                .expect("Input file must be specified");
+            let samples: Vec<String> = std::iter::once(in_file)
+                .chain(args.sample.iter().map(String::as_str))
+                .map(|path| read_from_file(path).expect("Error reading file"))
+                .collect();
+            let root_name = args.entity_name.clone().unwrap_or_else(|| {
+                let stem = Path::new(in_file).file_stem().unwrap_or_default();
+                utils::json_naming::pascal_case(&stem.to_string_lossy())
+            });
+            let cs_dto = json_to_cs_dto::json_to_cs_dto(
+                &samples,
+                &JsonDtoOptions {
+                    root_name,
+                    style: args.cs_style,
+                    naming_policy: args.naming_policy,
+                    base_namespace: base_namespace(&args),
+                },
+            )
+            .expect("Error inferring DTOs from JSON");
+            if let Some(ts_file) = &args.emit_ts {
+                let ts_interfaces = c_sharp_dto_to_ts_interface::csharp_dto_to_ts_interface(
+                    cs_dto.clone(),
+                    &[],
+                    &ts_options(&args),
+                )
+                .expect("Error converting DTOs to TypeScript");
+                File::create(Path::new(ts_file))?.write_all(ts_interfaces.as_bytes())?;
+                println!("created {ts_file}");
+            }
+            cs_dto
+        }
         Programs::UnitTestGenerator => unit_test_generator::run(
             read_from_file(args.in_file.expect("Input file must be specified").as_str())
                 .expect("Error reading file"),
-        ),
+        )
+        .expect("Error parsing C# file"),
     };
 
     // Write the processed content to the output file
     let out_file_name = args.out_file;
-    let mut output_file = File::create(&Path::new(out_file_name.clone().as_str()))?;
+    let mut output_file = File::create(Path::new(out_file_name.clone().as_str()))?;
     output_file.write_all(output_content.as_bytes())?;
 
     println!(
@@ -127,10 +375,119 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn ts_options(args: &Args) -> TsOptions {
+    TsOptions {
+        format: args.ts_format,
+        enum_style: args.enum_style,
+        enum_serialization: args.enum_serialization,
+        naming_policy: args.naming_policy,
+        nullable_policy: args.nullable_policy,
+        nullable_context: args.nullable_context,
+        type_map: match &args.type_map {
+            Some(path) => {
+                TsTypeMap::from_yaml(&read_from_file(path).expect("Error reading type map file"))
+                    .expect("Error parsing type map file")
+            }
+            None => TsTypeMap::default(),
+        },
+    }
+}
+
+/// The `--reference` files, parsed as C#.
+fn reference_files(args: &Args) -> Vec<CsFile> {
+    args.reference
+        .iter()
+        .map(|path| {
+            utils::csharp_parser::parse_csharp(&read_from_file(path).expect("Error reading file"))
+                .expect("Error parsing reference file")
+        })
+        .collect()
+}
+
+/// `--type-sortable-fields`, or with `--entity-file` the entity's selected properties
+/// followed by the `--type-sortable-fields` it doesn't declare.
+fn sortable_fields(args: &Args) -> Option<Vec<FieldWithType>> {
+    let Some(entity_file) = &args.entity_file else {
+        return args.type_sortable_fields.clone();
+    };
+    let entity = utils::csharp_parser::parse_csharp(
+        &read_from_file(entity_file).expect("Error reading entity file"),
+    )
+    .expect("Error parsing entity file");
+    let candidates =
+        entity_fields::entity_fields(&entity, args.entity_name.as_deref(), &reference_files(args))
+            .expect("Error reading entity fields");
+    let keep = entity_fields::field_filter(&args.include_field, &args.exclude_field)
+        .expect("Error parsing field patterns");
+
+    println!("Fields of {entity_file}:");
+    let mut fields = Vec::new();
+    for candidate in candidates {
+        let selected = keep(&candidate.field);
+        println!(
+            "  [{}] {} {}",
+            if selected { "x" } else { " " },
+            candidate.field_type,
+            candidate.field
+        );
+        if selected {
+            fields.push(candidate);
+        }
+    }
+    for extra in args.type_sortable_fields.iter().flatten() {
+        if !fields.iter().any(|field| field.field == extra.field) {
+            fields.push(extra.clone());
+        }
+    }
+    Some(fields)
+}
+
+/// `--project-config` with the `--filter` modes added.
+fn project_config(args: &Args) -> CrudProjectConfig {
+    let mut config = match &args.project_config {
+        Some(path) => CrudProjectConfig::from_yaml(
+            &read_from_file(path).expect("Error reading project config file"),
+        )
+        .expect("Error parsing project config file"),
+        None => CrudProjectConfig::default(),
+    };
+    config.filters.extend(
+        args.filter
+            .iter()
+            .map(|filter| (filter.field.clone(), filter.mode)),
+    );
+    config
+}
+
+/// `--base-name-space`, or the `BASE_NAMESPACE` environment variable.
+fn base_namespace(args: &Args) -> Option<String> {
+    args.base_name_space
+        .clone()
+        .or_else(|| std::env::var("BASE_NAMESPACE").ok())
+        .filter(|namespace| !namespace.is_empty())
+}
+
 fn read_from_file(input_file: &str) -> Result<String, Error> {
     // Open the input file and read its contents
-    let mut input_file = File::open(&Path::new(input_file))?;
+    let mut input_file = File::open(Path::new(input_file))?;
     let mut input_content = String::new();
     input_file.read_to_string(&mut input_content)?;
     Ok(input_content)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_with_type_from_str() {
+        let field = FieldWithType::from_str("name, string").unwrap();
+        assert_eq!(
+            (field.field.as_str(), field.field_type.as_str()),
+            ("name", "string")
+        );
+        assert!(FieldWithType::from_str("name").is_err());
+        assert!(FieldWithType::from_str("name,string,extra").is_err());
+        assert!(FieldWithType::from_str("name,").is_err());
+    }
+}
@@ -0,0 +1,661 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+};
+
+use crate::utils::{
+    json_naming::{pascal_case, NamingPolicy},
+    ts_parser::parse_typescript,
+    ts_syntax::{
+        TsDeclaration, TsDeclarationKind, TsEnumMember, TsIndexSignature, TsMember, TsType,
+    },
+};
+
+/// What TypeScript interfaces become in C#.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum CsDtoStyle {
+    /// `public class X { public string Name { get; set; } }`
+    #[default]
+    Class,
+    /// `public record X { public string Name { get; init; } }`
+    Record,
+}
+
+/// The C# type TypeScript's `number` maps to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum CsNumberType {
+    Int,
+    Long,
+    #[default]
+    Double,
+    Decimal,
+}
+
+impl CsNumberType {
+    fn keyword(&self) -> &'static str {
+        match self {
+            CsNumberType::Int => "int",
+            CsNumberType::Long => "long",
+            CsNumberType::Double => "double",
+            CsNumberType::Decimal => "decimal",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CsDtoOptions {
+    pub style: CsDtoStyle,
+    pub number_type: CsNumberType,
+    /// Policy the API serializes property names with; names it wouldn't produce
+    /// get a `[JsonPropertyName]`.
+    pub naming_policy: NamingPolicy,
+    /// `BASE_NAMESPACE`; the DTOs go in its `Core.Dtos` namespace.
+    pub base_namespace: Option<String>,
+}
+
+/// Converts the interfaces, object type aliases and enums of a TypeScript file into
+/// C# DTOs. Other type aliases are inlined where they are used, and unions of string
+/// literals become enums serialized as strings.
+pub fn ts_interface_to_cs_dto(content: String, options: &CsDtoOptions) -> Result<String, String> {
+    let file = parse_typescript(&content)?;
+    if file.declarations.is_empty() {
+        return Err("No interface, type or enum found in input file".to_string());
+    }
+    let generator = Generator {
+        options,
+        aliases: file
+            .declarations
+            .iter()
+            .filter_map(|decl| match &decl.kind {
+                TsDeclarationKind::Alias(ty) if !is_emitted_alias(ty) => {
+                    Some((decl.name.clone(), ty))
+                }
+                _ => None,
+            })
+            .collect(),
+        declarations: RefCell::new(Vec::new()),
+        emitted: RefCell::new(HashSet::new()),
+        uses_json_attributes: Cell::new(false),
+    };
+    for decl in &file.declarations {
+        generator.convert_declaration(decl);
+    }
+
+    let mut output = String::new();
+    if generator.uses_json_attributes.get() {
+        output.push_str("using System.Text.Json.Serialization;\n\n");
+    }
+    if let Some(base_namespace) = &options.base_namespace {
+        output.push_str(&format!("namespace {base_namespace}.Core.Dtos;\n\n"));
+    }
+    output.push_str(&generator.declarations.into_inner().join("\n"));
+    Ok(output)
+}
+
+/// Aliases of object types, intersections and string literal unions get a C#
+/// declaration of their own.
+fn is_emitted_alias(ty: &TsType) -> bool {
+    matches!(
+        ty.strip_null().0,
+        TsType::Object { .. } | TsType::Intersection(_)
+    ) || string_literals(&ty.strip_null().0).is_some()
+}
+
+struct Generator<'a> {
+    options: &'a CsDtoOptions,
+    /// Type aliases that are replaced by their type where used.
+    aliases: HashMap<String, &'a TsType>,
+    /// C# declarations in output order.
+    declarations: RefCell<Vec<String>>,
+    /// Names declared so far; an inline type never shadows an earlier declaration.
+    emitted: RefCell<HashSet<String>>,
+    uses_json_attributes: Cell<bool>,
+}
+
+impl Generator<'_> {
+    fn convert_declaration(&self, decl: &TsDeclaration) {
+        let doc = decl.doc.as_deref();
+        match &decl.kind {
+            TsDeclarationKind::Interface { extends, members } => self.class(
+                &decl.name,
+                &decl.type_params,
+                doc,
+                extends.first(),
+                members,
+                None,
+            ),
+            TsDeclarationKind::Alias(ty) if is_emitted_alias(ty) => {
+                self.inline_declaration(&decl.name, &decl.type_params, doc, &ty.strip_null().0);
+            }
+            TsDeclarationKind::Alias(_) => {}
+            TsDeclarationKind::Enum(members) => self.ts_enum(&decl.name, doc, members),
+        }
+    }
+
+    /// Declares an object type, intersection or string literal union under `name`.
+    fn inline_declaration(
+        &self,
+        name: &str,
+        type_params: &[String],
+        doc: Option<&str>,
+        ty: &TsType,
+    ) {
+        match ty {
+            TsType::Object { members, index } => {
+                self.class(name, type_params, doc, None, members, index.as_ref())
+            }
+            // `Base & { ... }`: the first named type is the base class, object members are merged
+            TsType::Intersection(items) => {
+                let base = items.iter().find(|item| item.name().is_some());
+                let mut members = Vec::new();
+                let mut index = None;
+                for item in items {
+                    if let TsType::Object {
+                        members: item_members,
+                        index: item_index,
+                    } = item
+                    {
+                        members.extend(item_members.iter().cloned());
+                        index = index.or(item_index.clone());
+                    }
+                }
+                self.class(name, type_params, doc, base, &members, index.as_ref());
+            }
+            _ => {
+                let literals = string_literals(ty).unwrap_or_default();
+                self.string_enum(name, doc, &literals);
+            }
+        }
+    }
+
+    /// Emits a class or record. Properties declared without `?` are `required`; `?` and
+    /// `| null` make the type nullable. An index signature next to named members
+    /// collects the remaining JSON properties through `[JsonExtensionData]`.
+    fn class(
+        &self,
+        name: &str,
+        type_params: &[String],
+        doc: Option<&str>,
+        base: Option<&TsType>,
+        members: &[TsMember],
+        index: Option<&TsIndexSignature>,
+    ) {
+        if !self.emitted.borrow_mut().insert(name.to_string()) {
+            return;
+        }
+        // reserve the slot so inline types declared by members follow their owner
+        let slot = self.declarations.borrow().len();
+        self.declarations.borrow_mut().push(String::new());
+
+        let keyword = match self.options.style {
+            CsDtoStyle::Class => "class",
+            CsDtoStyle::Record => "record",
+        };
+        let setter = match self.options.style {
+            CsDtoStyle::Class => "set",
+            CsDtoStyle::Record => "init",
+        };
+        let type_params = match type_params.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", type_params.join(", ")),
+        };
+        let base = base
+            .map(|base| format!(" : {}", self.cs_type(base, name)))
+            .unwrap_or_default();
+
+        let mut cs_class = xml_doc(doc, "");
+        cs_class.push_str(&format!("public {keyword} {name}{type_params}{base}\n{{\n"));
+        let mut used_names = HashSet::from([name.to_string()]);
+        let mut properties = Vec::new();
+        for member in members {
+            let mut property_name = pascal_case(&member.name);
+            let (ty, is_null) = member.ty.strip_null();
+            let cs_type = self.cs_type(&ty, &format!("{name}{property_name}"));
+            // a member can't share its class's name, nor another member's
+            if property_name == name {
+                property_name.push_str("Value");
+            }
+            let base_name = property_name.clone();
+            let mut n = 2;
+            while !used_names.insert(property_name.clone()) {
+                property_name = format!("{base_name}{n}");
+                n += 1;
+            }
+            let nullable_marker = match member.optional || is_null {
+                true => "?",
+                false => "",
+            };
+            let required = match member.optional {
+                true => "",
+                false => "required ",
+            };
+            let mut property = xml_doc(member.doc.as_deref(), "    ");
+            if self.options.naming_policy.apply(&property_name) != member.name {
+                self.uses_json_attributes.set(true);
+                property.push_str(&format!(
+                    "    [JsonPropertyName({})]\n",
+                    cs_string_literal(&member.name)
+                ));
+            }
+            property.push_str(&format!(
+                "    public {required}{cs_type}{nullable_marker} {property_name} {{ get; {setter}; }}\n"
+            ));
+            properties.push(property);
+        }
+        if let Some((key, value)) = index.filter(|_| !members.is_empty()) {
+            self.uses_json_attributes.set(true);
+            properties.push(format!(
+                "    [JsonExtensionData]\n    public Dictionary<{}, {}>? ExtensionData {{ get; {setter}; }}\n",
+                self.cs_type(key, name),
+                self.cs_type(value, name)
+            ));
+        }
+        cs_class.push_str(&properties.join("\n"));
+        cs_class.push_str("}\n");
+        self.declarations.borrow_mut()[slot] = cs_class;
+    }
+
+    /// Emits a TypeScript `enum`. String-valued members keep their values on the wire.
+    fn ts_enum(&self, name: &str, doc: Option<&str>, members: &[TsEnumMember]) {
+        let string_values: Option<Vec<String>> = members
+            .iter()
+            .map(|member| {
+                let value = member.value.as_deref()?;
+                let value = value.strip_prefix('\'')?.strip_suffix('\'')?;
+                Some(value.to_string())
+            })
+            .collect();
+        if let Some(values) = string_values.filter(|values| !values.is_empty()) {
+            let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+            return self.enum_declaration(name, doc, &names, &values);
+        }
+
+        if !self.emitted.borrow_mut().insert(name.to_string()) {
+            return;
+        }
+        let mut cs_enum = xml_doc(doc, "");
+        cs_enum.push_str(&format!("public enum {name}\n{{\n"));
+        for member in members {
+            cs_enum.push_str(&xml_doc(member.doc.as_deref(), "    "));
+            match &member.value {
+                Some(value) => {
+                    cs_enum.push_str(&format!("    {} = {value},\n", pascal_case(&member.name)))
+                }
+                None => cs_enum.push_str(&format!("    {},\n", pascal_case(&member.name))),
+            }
+        }
+        cs_enum.push_str("}\n");
+        self.declarations.borrow_mut().push(cs_enum);
+    }
+
+    /// An enum for a union of string literals, named after the literals.
+    fn string_enum(&self, name: &str, doc: Option<&str>, literals: &[String]) {
+        let names: Vec<&str> = literals.iter().map(String::as_str).collect();
+        self.enum_declaration(name, doc, &names, literals);
+    }
+
+    /// An enum written as strings: member names are PascalCased, numbered when two
+    /// values PascalCase alike, and a value that differs from its member name gets a
+    /// `[JsonStringEnumMemberName]`.
+    fn enum_declaration(&self, name: &str, doc: Option<&str>, names: &[&str], values: &[String]) {
+        if !self.emitted.borrow_mut().insert(name.to_string()) {
+            return;
+        }
+        self.uses_json_attributes.set(true);
+        let mut cs_enum = xml_doc(doc, "");
+        cs_enum.push_str(&format!(
+            "[JsonConverter(typeof(JsonStringEnumConverter))]\npublic enum {name}\n{{\n"
+        ));
+        let mut used_names = HashSet::new();
+        for (member_name, value) in names.iter().zip(values) {
+            let base_name = pascal_case(member_name);
+            let mut member_name = base_name.clone();
+            let mut n = 2;
+            while !used_names.insert(member_name.clone()) {
+                member_name = format!("{base_name}{n}");
+                n += 1;
+            }
+            if member_name != *value {
+                cs_enum.push_str(&format!(
+                    "    [JsonStringEnumMemberName({})]\n",
+                    cs_string_literal(value)
+                ));
+            }
+            cs_enum.push_str(&format!("    {member_name},\n"));
+        }
+        cs_enum.push_str("}\n");
+        self.declarations.borrow_mut().push(cs_enum);
+    }
+
+    /// Maps a TypeScript type to C#. Inline object types and string literal unions are
+    /// declared as `context`, the owner's name followed by the property's.
+    fn cs_type(&self, ts_type: &TsType, context: &str) -> String {
+        let (ts_type, is_null) = ts_type.strip_null();
+        let nullable_marker = if is_null { "?" } else { "" };
+        let cs_type = match &ts_type {
+            TsType::Named { name, args } => self.named_type(name, args, context),
+            TsType::Array(item) => format!("List<{}>", self.cs_type(item, &format!("{context}Item"))),
+            TsType::Tuple(items) => {
+                let items: HashSet<String> = items
+                    .iter()
+                    .map(|item| self.cs_type(item, &format!("{context}Item")))
+                    .collect();
+                match items.len() {
+                    1 => format!("List<{}>", items.into_iter().next().unwrap()),
+                    _ => "List<object>".to_string(),
+                }
+            }
+            TsType::Object { members, index } if members.is_empty() => match index {
+                Some((key, value)) => format!(
+                    "Dictionary<{}, {}>",
+                    self.cs_type(key, context),
+                    self.cs_type(value, context)
+                ),
+                None => "object".to_string(),
+            },
+            TsType::Object { .. } | TsType::Intersection(_) => {
+                self.inline_declaration(context, &[], None, &ts_type);
+                context.to_string()
+            }
+            TsType::StringLiteral(_) => "string".to_string(),
+            TsType::Literal(literal) => self.literal_type(literal).to_string(),
+            TsType::Union(items) => match string_literals(&ts_type) {
+                Some(literals) => {
+                    self.string_enum(context, None, &literals);
+                    context.to_string()
+                }
+                None => match items.as_slice() {
+                    [TsType::Literal(first), rest @ ..]
+                        if rest.iter().all(|item| {
+                            matches!(item, TsType::Literal(l) if self.literal_type(l) == self.literal_type(first))
+                        }) =>
+                    {
+                        self.literal_type(first).to_string()
+                    }
+                    _ => "object".to_string(),
+                },
+            },
+        };
+        format!("{cs_type}{nullable_marker}")
+    }
+
+    fn named_type(&self, name: &str, args: &[TsType], context: &str) -> String {
+        let arg = |index: usize| {
+            args.get(index)
+                .map(|arg| self.cs_type(arg, context))
+                .unwrap_or("object".to_string())
+        };
+        match (name, args.len()) {
+            ("string", 0) => "string".to_string(),
+            ("number", 0) => self.options.number_type.keyword().to_string(),
+            ("boolean", 0) => "bool".to_string(),
+            ("bigint", 0) => "long".to_string(),
+            ("Date", 0) => "DateTime".to_string(),
+            ("Record" | "Map", 2) => format!("Dictionary<{}, {}>", arg(0), arg(1)),
+            ("Set", 1) => format!("HashSet<{}>", arg(0)),
+            ("Partial" | "Readonly" | "Required" | "NonNullable", 1) => arg(0),
+            (alias, 0) if self.aliases.contains_key(alias) => {
+                self.cs_type(self.aliases[alias], alias)
+            }
+            (
+                "any" | "unknown" | "object" | "Object" | "Function" | "never" | "void" | "symbol"
+                | "null" | "undefined",
+                _,
+            ) => "object".to_string(),
+            (name, 0) => name.to_string(),
+            (name, _) => {
+                let args: Vec<String> = (0..args.len()).map(arg).collect();
+                format!("{name}<{}>", args.join(", "))
+            }
+        }
+    }
+
+    fn literal_type(&self, literal: &str) -> &'static str {
+        match literal {
+            "true" | "false" => "bool",
+            _ => self.options.number_type.keyword(),
+        }
+    }
+}
+
+/// The values of a union made only of string literals.
+fn string_literals(ts_type: &TsType) -> Option<Vec<String>> {
+    let TsType::Union(items) = ts_type else {
+        return None;
+    };
+    items
+        .iter()
+        .map(|item| match item {
+            TsType::StringLiteral(value) => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `/// <summary>` from a JSDoc comment; `@deprecated` becomes `[Obsolete]` and
+/// other tags are dropped.
+fn xml_doc(doc: Option<&str>, indent: &str) -> String {
+    let Some(doc) = doc else {
+        return String::new();
+    };
+    let mut summary = Vec::new();
+    let mut deprecated = None;
+    let mut in_tag = false;
+    for line in doc.lines() {
+        if let Some(tag) = line.strip_prefix('@') {
+            in_tag = true;
+            if let Some(reason) = tag.strip_prefix("deprecated") {
+                deprecated = Some(reason.trim().to_string());
+            }
+        } else if !in_tag {
+            summary.push(line);
+        }
+    }
+    while summary.last().is_some_and(|line| line.is_empty()) {
+        summary.pop();
+    }
+
+    let mut xml = String::new();
+    if !summary.is_empty() {
+        xml.push_str(&format!("{indent}/// <summary>\n"));
+        for line in summary {
+            let line = line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            xml.push_str(&format!("{indent}/// {line}\n").replace("/// \n", "///\n"));
+        }
+        xml.push_str(&format!("{indent}/// </summary>\n"));
+    }
+    match deprecated {
+        Some(reason) if !reason.is_empty() => xml.push_str(&format!(
+            "{indent}[Obsolete({})]\n",
+            cs_string_literal(&reason)
+        )),
+        Some(_) => xml.push_str(&format!("{indent}[Obsolete]\n")),
+        None => {}
+    }
+    xml
+}
+
+pub fn cs_string_literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(ts: &str, options: &CsDtoOptions) -> String {
+        ts_interface_to_cs_dto(ts.to_string(), options).unwrap()
+    }
+
+    #[test]
+    fn test_interface_to_class() {
+        let cs = convert(
+            r#"
+            /** A customer. */
+            export interface CustomerDto extends EntityDto {
+                name: string;
+                email?: string;
+                age: number | null;
+                tags: string[];
+                scores: Array<number>;
+                createdAt: Date;
+                status: 'active' | 'on-hold';
+                /** @deprecated use name */
+                first_name?: string;
+                address: { street: string };
+            }
+            "#,
+            &CsDtoOptions {
+                base_namespace: Some("Acme".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            cs,
+            r#"using System.Text.Json.Serialization;
+
+namespace Acme.Core.Dtos;
+
+/// <summary>
+/// A customer.
+/// </summary>
+public class CustomerDto : EntityDto
+{
+    public required string Name { get; set; }
+
+    public string? Email { get; set; }
+
+    public required double? Age { get; set; }
+
+    public required List<string> Tags { get; set; }
+
+    public required List<double> Scores { get; set; }
+
+    public required DateTime CreatedAt { get; set; }
+
+    public required CustomerDtoStatus Status { get; set; }
+
+    [Obsolete("use name")]
+    [JsonPropertyName("first_name")]
+    public string? FirstName { get; set; }
+
+    public required CustomerDtoAddress Address { get; set; }
+}
+
+[JsonConverter(typeof(JsonStringEnumConverter))]
+public enum CustomerDtoStatus
+{
+    [JsonStringEnumMemberName("active")]
+    Active,
+    [JsonStringEnumMemberName("on-hold")]
+    OnHold,
+}
+
+public class CustomerDtoAddress
+{
+    public required string Street { get; set; }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_records_number_type_and_aliases() {
+        let cs = convert(
+            r#"
+            type Id = string;
+            export type Role = 'admin' | 'user';
+            export type Page<T> = { items: T[]; total: number };
+            export type Admin = UserDto & { permissions: Record<string, boolean> };
+            export interface UserDto { id: Id; role: Role; meta: { [key: string]: unknown } }
+            "#,
+            &CsDtoOptions {
+                style: CsDtoStyle::Record,
+                number_type: CsNumberType::Int,
+                ..Default::default()
+            },
+        );
+        assert!(cs.contains(
+            "public enum Role\n{\n    [JsonStringEnumMemberName(\"admin\")]\n    Admin,"
+        ));
+        assert!(cs.contains(
+            "public record Page<T>\n{\n    public required List<T> Items { get; init; }\n\n    public required int Total { get; init; }\n}"
+        ));
+        assert!(cs.contains(
+            "public record Admin : UserDto\n{\n    public required Dictionary<string, bool> Permissions { get; init; }\n}"
+        ));
+        assert!(cs.contains("public required string Id { get; init; }"));
+        assert!(cs.contains("public required Role Role { get; init; }"));
+        assert!(cs.contains("public required Dictionary<string, object> Meta { get; init; }"));
+        assert!(!cs.contains("namespace"));
+    }
+
+    #[test]
+    fn test_typescript_enums() {
+        let cs = convert(
+            "enum Level { Low = 1, High = 2 }\nenum Color { Red = 'red', Blue = 'BLUE' }",
+            &CsDtoOptions::default(),
+        );
+        assert!(cs.contains("public enum Level\n{\n    Low = 1,\n    High = 2,\n}"));
+        assert!(cs.contains(
+            "public enum Color\n{\n    [JsonStringEnumMemberName(\"red\")]\n    Red,\n    [JsonStringEnumMemberName(\"BLUE\")]\n    Blue,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_member_named_like_its_class_is_renamed() {
+        let cs = convert(
+            "interface Role { role: string; Role: number }",
+            &CsDtoOptions::default(),
+        );
+        assert!(cs.contains(
+            "public class Role\n{\n    [JsonPropertyName(\"role\")]\n    public required string RoleValue { get; set; }\n\n    [JsonPropertyName(\"Role\")]\n    public required double RoleValue2 { get; set; }\n}"
+        ));
+    }
+
+    #[test]
+    fn test_string_literals_pascal_casing_alike_get_numbered() {
+        let cs = convert(
+            "type Mode = 'a-b' | 'a_b' | 'AB';",
+            &CsDtoOptions::default(),
+        );
+        assert!(cs.contains(
+            "public enum Mode\n{\n    [JsonStringEnumMemberName(\"a-b\")]\n    AB,\n    [JsonStringEnumMemberName(\"a_b\")]\n    AB2,\n    [JsonStringEnumMemberName(\"AB\")]\n    AB3,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_flag_enum_keeps_shift_operators() {
+        let cs = convert(
+            "enum Permission { None = 0, Read = 1 << 0, Write = 1 << 1, All = Read | Write, Top = -1 >>> 28 }",
+            &CsDtoOptions::default(),
+        );
+        assert!(cs.contains(
+            "public enum Permission\n{\n    None = 0,\n    Read = 1 << 0,\n    Write = 1 << 1,\n    All = Read | Write,\n    Top = - 1 >>> 28,\n}"
+        ));
+    }
+
+    #[test]
+    fn test_naming_policy_decides_property_name_attributes() {
+        let ts = "interface Dto { first_name: string; lastName: string }";
+        let snake = convert(
+            ts,
+            &CsDtoOptions {
+                naming_policy: NamingPolicy::Snake,
+                ..Default::default()
+            },
+        );
+        assert!(!snake.contains("[JsonPropertyName(\"first_name\")]"));
+        assert!(snake.contains("[JsonPropertyName(\"lastName\")]"));
+    }
+
+    #[test]
+    fn test_input_without_declarations_is_an_error() {
+        assert!(
+            ts_interface_to_cs_dto("const x = 1;".to_string(), &CsDtoOptions::default()).is_err()
+        );
+    }
+}
@@ -1,15 +1,15 @@
-use std::fmt::format;
-
 use itertools::Itertools;
 
 use crate::utils::{
-    get_class_name::get_class_name, get_constructor_interfaces::get_constructor_interfaces,
+    csharp_parser::parse_csharp, get_class_name::get_class_name,
+    get_constructor_interfaces::get_constructor_interfaces,
     interface_to_name_transform::interface_to_name_transform,
 };
 
-pub fn run(file_text: String) -> String {
-    let class_name = get_class_name(file_text.clone());
-    let constructor_interfaces = get_constructor_interfaces(&file_text.clone());
+pub fn run(file_text: String) -> Result<String, String> {
+    let file = parse_csharp(&file_text)?;
+    let class_name = get_class_name(&file);
+    let constructor_interfaces = get_constructor_interfaces(&file);
 
     if let Some(class_name) = class_name {
         let include_transaction = constructor_interfaces
@@ -37,12 +37,12 @@ pub fn run(file_text: String) -> String {
                 format!(
                     "private readonly Mock<{}> _{} = new();",
                     interface.clone(),
-                    interface_to_name_transform(&interface)
+                    interface_to_name_transform(interface)
                 )
             })
             .join("\n\t");
 
-        return format!(
+        return Ok(format!(
             r#"
 {test_class_name}
 {{    
@@ -71,9 +71,9 @@ pub fn run(file_text: String) -> String {
 
 }}
 "#,
-        );
+        ));
     }
-    todo!()
+    Err("No public class found in input file".to_string())
 }
 
 fn build_mock_object(
@@ -95,8 +95,7 @@ fn build_mock_object(
         .join("\n\t\t");
 
     let transaction_setup = if include_transaction {
-        format!(
-            r#"
+        r#"
         _unitOfWork.Setup(uow => uow.BeginTransaction(It.IsAny<CancellationToken>()))
             .ReturnsAsync(_transaction.Object);
 
@@ -105,8 +104,7 @@ fn build_mock_object(
 
         _transaction.Setup(tran => tran.RollbackAsync(It.IsAny<CancellationToken>()))
             .Returns(Task.CompletedTask);
-        "#
-        )
+        "#.to_string()
     } else {
         String::new()
     };
@@ -0,0 +1,485 @@
+/// A single lexical token from a C# source file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Byte offset of the first character of the token.
+    pub start: usize,
+    /// Byte offset one past the last character of the token.
+    pub end: usize,
+    /// 1-based line the token starts on.
+    pub line: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+    /// Identifiers and keywords. Verbatim identifiers (`@class`) are stored without the `@`.
+    Identifier(String),
+    /// Operators and punctuation. `>>` is always lexed as two `>` so nested generics close cleanly.
+    Punct(String),
+    /// String literal as written in the source, including quotes and prefixes.
+    String(String),
+    Char(String),
+    Number(String),
+    /// Text of a `///` comment line with the slashes removed.
+    DocComment(String),
+    /// Preprocessor line with the `#` removed, e.g. `nullable enable`.
+    Directive(String),
+}
+
+const MULTI_CHAR_PUNCT: [&str; 24] = [
+    "??=", "<<=", "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "::", "++", "--", "+=",
+    "-=", "*=", "/=", "%=", "&=", "|=", "^=", "->", "<<",
+];
+
+/// Splits C# source into tokens. Regular comments and whitespace are dropped,
+/// doc comments and preprocessor directives are kept as tokens.
+pub fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    Lexer {
+        src: source,
+        bytes: source.as_bytes(),
+        pos: 0,
+        line: 1,
+    }
+    .run()
+}
+
+struct Lexer<'a> {
+    src: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn run(mut self) -> Result<Vec<Token>, String> {
+        let mut tokens = Vec::new();
+        let mut line_start = true;
+
+        while self.pos < self.bytes.len() {
+            let c = self.bytes[self.pos];
+
+            if c == b'\n' {
+                self.line += 1;
+                self.pos += 1;
+                line_start = true;
+                continue;
+            }
+            if c.is_ascii_whitespace() {
+                self.pos += 1;
+                continue;
+            }
+
+            let start = self.pos;
+            let line = self.line;
+
+            let kind = if c == b'#' && line_start {
+                let text = self.take_line(start + 1);
+                TokenKind::Directive(text.trim().to_string())
+            } else if self.starts_with("///") && !self.starts_with("////") {
+                let text = self.take_line(start + 3);
+                TokenKind::DocComment(
+                    text.strip_prefix(' ')
+                        .unwrap_or(text)
+                        .trim_end()
+                        .to_string(),
+                )
+            } else if self.starts_with("//") {
+                self.take_line(start);
+                continue;
+            } else if self.starts_with("/*") {
+                self.skip_block_comment()?;
+                continue;
+            } else if self.at_string_start() {
+                self.scan_string()?;
+                TokenKind::String(self.src[start..self.pos].to_string())
+            } else if c == b'\'' {
+                self.scan_char()?;
+                TokenKind::Char(self.src[start..self.pos].to_string())
+            } else if c.is_ascii_digit()
+                || (c == b'.' && self.peek(1).is_some_and(|n| n.is_ascii_digit()))
+            {
+                self.scan_number();
+                TokenKind::Number(self.src[start..self.pos].to_string())
+            } else if c == b'@' || is_ident_start(self.current_char()) {
+                if c == b'@' {
+                    self.pos += 1;
+                }
+                let ident_start = self.pos;
+                while self.pos < self.bytes.len() && is_ident_part(self.current_char()) {
+                    self.pos += self.current_char().len_utf8();
+                }
+                if ident_start == self.pos {
+                    return Err(format!("unexpected '@' on line {line}"));
+                }
+                TokenKind::Identifier(self.src[ident_start..self.pos].to_string())
+            } else {
+                let punct = MULTI_CHAR_PUNCT
+                    .iter()
+                    .find(|p| self.starts_with(p))
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| self.current_char().to_string());
+                self.pos += punct.len();
+                TokenKind::Punct(punct)
+            };
+
+            line_start = false;
+            tokens.push(Token {
+                kind,
+                start,
+                end: self.pos,
+                line,
+            });
+        }
+
+        Ok(tokens)
+    }
+
+    fn current_char(&self) -> char {
+        self.src[self.pos..].chars().next().unwrap_or('\0')
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.pos + offset).copied()
+    }
+
+    /// Compares bytes, so it can be asked anywhere, even in the middle of a multi-byte character.
+    fn starts_with(&self, s: &str) -> bool {
+        self.bytes[self.pos..].starts_with(s.as_bytes())
+    }
+
+    /// Consumes the rest of the line (without the newline) and returns it from `from`.
+    fn take_line(&mut self, from: usize) -> &'a str {
+        while self.pos < self.bytes.len() && self.bytes[self.pos] != b'\n' {
+            self.pos += 1;
+        }
+        self.src[from..self.pos].trim_end_matches('\r')
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), String> {
+        let line = self.line;
+        self.pos += 2;
+        while self.pos < self.bytes.len() {
+            if self.starts_with("*/") {
+                self.pos += 2;
+                return Ok(());
+            }
+            if self.bytes[self.pos] == b'\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+        Err(format!(
+            "unterminated block comment starting on line {line}"
+        ))
+    }
+
+    fn at_string_start(&self) -> bool {
+        let prefix_len = self.bytes[self.pos..]
+            .iter()
+            .take_while(|b| **b == b'$' || **b == b'@')
+            .count();
+        // `@` alone is a verbatim identifier prefix, not a string prefix
+        self.peek(prefix_len) == Some(b'"')
+    }
+
+    fn scan_string(&mut self) -> Result<(), String> {
+        let line = self.line;
+        let mut verbatim = false;
+        let mut interpolation_braces = 0;
+        while let Some(b) = self.peek(0) {
+            match b {
+                b'@' => verbatim = true,
+                b'$' => interpolation_braces += 1,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+
+        let quotes = self.bytes[self.pos..]
+            .iter()
+            .take_while(|b| **b == b'"')
+            .count();
+        if quotes >= 3 {
+            return self.scan_raw_string(quotes, interpolation_braces, line);
+        }
+
+        self.pos += 1;
+        while let Some(b) = self.peek(0) {
+            match b {
+                b'"' if verbatim && self.peek(1) == Some(b'"') => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                b'\\' if !verbatim => self.pos += 2,
+                b'{' if interpolation_braces > 0 && self.peek(1) == Some(b'{') => self.pos += 2,
+                b'{' if interpolation_braces > 0 => self.scan_interpolation_hole()?,
+                b'\n' if !verbatim => break,
+                b'\n' => {
+                    self.line += 1;
+                    self.pos += 1;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        Err(format!("unterminated string literal on line {line}"))
+    }
+
+    /// C# 11 raw string literals: `"""..."""` with any number (3+) of quotes.
+    fn scan_raw_string(
+        &mut self,
+        quotes: usize,
+        interpolation_braces: usize,
+        line: usize,
+    ) -> Result<(), String> {
+        self.pos += quotes;
+        let closing = "\"".repeat(quotes);
+        let hole = "{".repeat(interpolation_braces.max(1));
+        while self.pos < self.bytes.len() {
+            if self.starts_with(&closing) {
+                self.pos += quotes;
+                return Ok(());
+            }
+            if interpolation_braces > 0 && self.starts_with(&hole) {
+                self.pos += interpolation_braces - 1;
+                self.scan_interpolation_hole()?;
+                continue;
+            }
+            if self.bytes[self.pos] == b'\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+        Err(format!("unterminated raw string literal on line {line}"))
+    }
+
+    /// Skips an interpolation hole `{ ... }`, which may itself contain strings and braces.
+    fn scan_interpolation_hole(&mut self) -> Result<(), String> {
+        let line = self.line;
+        let mut depth = 0;
+        while let Some(b) = self.peek(0) {
+            if self.at_string_start() {
+                self.scan_string()?;
+                continue;
+            }
+            match b {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return Ok(());
+                    }
+                }
+                b'\'' => {
+                    self.scan_char()?;
+                    continue;
+                }
+                b'\n' => self.line += 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Err(format!("unterminated interpolation on line {line}"))
+    }
+
+    fn scan_char(&mut self) -> Result<(), String> {
+        let line = self.line;
+        self.pos += 1;
+        while let Some(b) = self.peek(0) {
+            match b {
+                b'\\' => self.pos += 2,
+                b'\'' => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                b'\n' => break,
+                _ => self.pos += 1,
+            }
+        }
+        Err(format!("unterminated character literal on line {line}"))
+    }
+
+    fn scan_number(&mut self) {
+        let hex = self.starts_with("0x") || self.starts_with("0X");
+        while let Some(b) = self.peek(0) {
+            let exponent_sign =
+                (b == b'+' || b == b'-') && matches!(self.bytes[self.pos - 1], b'e' | b'E') && !hex;
+            let decimal_point = b == b'.' && self.peek(1).is_some_and(|n| n.is_ascii_digit());
+            if b.is_ascii_alphanumeric() || b == b'_' || decimal_point || exponent_sign {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_ident_part(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    fn ident(s: &str) -> TokenKind {
+        TokenKind::Identifier(s.to_string())
+    }
+
+    fn punct(s: &str) -> TokenKind {
+        TokenKind::Punct(s.to_string())
+    }
+
+    #[test]
+    fn test_property_declaration() {
+        assert_eq!(
+            kinds("public int? Id { get; set; }"),
+            vec![
+                ident("public"),
+                ident("int"),
+                punct("?"),
+                ident("Id"),
+                punct("{"),
+                ident("get"),
+                punct(";"),
+                ident("set"),
+                punct(";"),
+                punct("}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_generics_close_separately() {
+        assert_eq!(
+            kinds("List<List<int>>"),
+            vec![
+                ident("List"),
+                punct("<"),
+                ident("List"),
+                punct("<"),
+                ident("int"),
+                punct(">"),
+                punct(">"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_are_dropped_and_doc_comments_kept() {
+        let source = r#"
+            // a comment with a { brace
+            /* block
+               comment } */
+            /// <summary>Docs</summary>
+            public int Id;
+        "#;
+        assert_eq!(
+            kinds(source),
+            vec![
+                TokenKind::DocComment("<summary>Docs</summary>".to_string()),
+                ident("public"),
+                ident("int"),
+                ident("Id"),
+                punct(";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strings_containing_braces_and_quotes() {
+        let source = r#"a = "x { \" }"; b = @"c:\path "" }"; c = $"{Name} {{ {(x ? "}" : "{")}";"#;
+        let strings: Vec<TokenKind> = kinds(source)
+            .into_iter()
+            .filter(|k| matches!(k, TokenKind::String(_)))
+            .collect();
+        assert_eq!(
+            strings,
+            vec![
+                TokenKind::String(r#""x { \" }""#.to_string()),
+                TokenKind::String(r#"@"c:\path "" }""#.to_string()),
+                TokenKind::String(r#"$"{Name} {{ {(x ? "}" : "{")}""#.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_string_literal() {
+        let source = "var json = \"\"\"\n  { \"a\": \"}\" }\n  \"\"\";";
+        let tokens = tokenize(source).unwrap();
+        assert_eq!(tokens.len(), 5);
+        assert!(matches!(tokens[3].kind, TokenKind::String(_)));
+        assert_eq!(tokens[4].line, 3);
+    }
+
+    #[test]
+    fn test_directives_and_verbatim_identifiers() {
+        assert_eq!(
+            kinds("#nullable enable\npublic string @class;"),
+            vec![
+                TokenKind::Directive("nullable enable".to_string()),
+                ident("public"),
+                ident("string"),
+                ident("class"),
+                punct(";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbers_and_chars() {
+        assert_eq!(
+            kinds("x = 1.5e-3m + 0xFF + 'a' + '\\'';"),
+            vec![
+                ident("x"),
+                punct("="),
+                TokenKind::Number("1.5e-3m".to_string()),
+                punct("+"),
+                TokenKind::Number("0xFF".to_string()),
+                punct("+"),
+                TokenKind::Char("'a'".to_string()),
+                punct("+"),
+                TokenKind::Char("'\\''".to_string()),
+                punct(";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_byte_text_in_comments_strings_and_holes() {
+        let source = "/* café — note */ a = \"\"\"naïve — \"\"\"; b = $\"{naïve}\"; c = $\"\"\"{f(\"é\")} ü\"\"\"; d = 'é';";
+        let strings: Vec<TokenKind> = kinds(source)
+            .into_iter()
+            .filter(|k| matches!(k, TokenKind::String(_) | TokenKind::Char(_)))
+            .collect();
+        assert_eq!(
+            strings,
+            vec![
+                TokenKind::String("\"\"\"naïve — \"\"\"".to_string()),
+                TokenKind::String("$\"{naïve}\"".to_string()),
+                TokenKind::String("$\"\"\"{f(\"é\")} ü\"\"\"".to_string()),
+                TokenKind::Char("'é'".to_string()),
+            ]
+        );
+        assert_eq!(kinds("/* café — note */ x")[0], ident("x"));
+    }
+
+    #[test]
+    fn test_unterminated_string_is_an_error() {
+        assert!(tokenize("var x = \"oops;\n").is_err());
+        assert!(tokenize("/* never closed").is_err());
+    }
+}
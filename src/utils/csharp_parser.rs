@@ -0,0 +1,1202 @@
+use crate::utils::{
+    csharp_lexer::{tokenize, Token, TokenKind},
+    csharp_syntax::{
+        Attribute, AttributeArg, Constructor, CsFile, EnumMember, Member, Method, Parameter,
+        Property, TypeDecl, TypeKind, TypeRef,
+    },
+};
+
+const MODIFIERS: [&str; 20] = [
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "readonly",
+    "const",
+    "virtual",
+    "override",
+    "abstract",
+    "sealed",
+    "new",
+    "partial",
+    "async",
+    "extern",
+    "unsafe",
+    "volatile",
+    "required",
+    "file",
+    "fixed",
+];
+
+const PARAMETER_MODIFIERS: [&str; 7] = ["ref", "out", "in", "params", "this", "scoped", "readonly"];
+
+/// Parses C# source into a [`CsFile`]. Anything that is not a declaration
+/// (method bodies, initializers, top-level statements) is skipped.
+/// Input that ends in the middle of a type declaration keeps what was parsed so far,
+/// so snippets such as a lone class header can still be queried.
+pub fn parse_csharp(source: &str) -> Result<CsFile, String> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        src: source,
+        nullable_context: nullable_context(&tokens),
+        tokens,
+        pos: 0,
+    };
+    let mut file = CsFile::default();
+    parser.parse_declarations(&mut file, None)?;
+    Ok(file)
+}
+
+/// The nullable annotation context in effect at each token, following
+/// `#nullable enable|disable|restore [annotations]` directives.
+fn nullable_context(tokens: &[Token]) -> Vec<Option<bool>> {
+    let mut context = None;
+    let mut contexts = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if let TokenKind::Directive(directive) = &token.kind {
+            let mut words = directive.split_whitespace();
+            if words.next() == Some("nullable") {
+                let setting = words.next();
+                if matches!(words.next(), None | Some("annotations")) {
+                    context = match setting {
+                        Some("enable") => Some(true),
+                        Some("disable") => Some(false),
+                        _ => None,
+                    };
+                }
+            }
+        }
+        contexts.push(context);
+    }
+    contexts
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    nullable_context: Vec<Option<bool>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses namespace members until end of input, or until the closing `}` of a block namespace.
+    fn parse_declarations(
+        &mut self,
+        file: &mut CsFile,
+        namespace: Option<String>,
+    ) -> Result<(), String> {
+        let in_block = namespace.is_some();
+        loop {
+            self.skip_trivia();
+            let Some(token) = self.peek() else {
+                return match in_block {
+                    true => Err("unexpected end of file inside namespace".to_string()),
+                    false => Ok(()),
+                };
+            };
+
+            if self.is_punct("}") && in_block {
+                self.pos += 1;
+                return Ok(());
+            }
+
+            match &token.kind {
+                TokenKind::Identifier(word) if word == "using" || word == "global" => {
+                    if self.eat_ident("global") {
+                        self.expect_ident("using")?;
+                    } else {
+                        self.pos += 1;
+                    }
+                    let using = self.expression_until(&[";"])?;
+                    self.expect_punct(";")?;
+                    file.usings.push(using);
+                }
+                TokenKind::Identifier(word) if word == "extern" && self.is_ident_at(1, "alias") => {
+                    self.skip_past(";")?;
+                }
+                TokenKind::Identifier(word) if word == "namespace" => {
+                    self.pos += 1;
+                    let name = self.qualified_name()?;
+                    let name = match &namespace {
+                        Some(outer) => format!("{outer}.{name}"),
+                        None => name,
+                    };
+                    if file.namespace.is_none() {
+                        file.namespace = Some(name.clone());
+                    }
+                    if self.eat_punct("{") {
+                        self.parse_declarations(file, Some(name))?;
+                    } else {
+                        self.expect_punct(";")?;
+                    }
+                }
+                _ => {
+                    let doc = self.doc_comment();
+                    let attributes = self.attributes()?;
+                    let modifiers = self.modifiers();
+                    if self.at_type_keyword() {
+                        let decl = self.type_decl(doc, attributes, modifiers)?;
+                        file.types.push(decl);
+                    } else if !attributes.is_empty() || !modifiers.is_empty() {
+                        // delegates and other non-type declarations
+                        self.skip_member()?;
+                    } else {
+                        // top-level statements
+                        self.skip_statement()?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn type_decl(
+        &mut self,
+        doc: Vec<String>,
+        attributes: Vec<Attribute>,
+        modifiers: Vec<String>,
+    ) -> Result<TypeDecl, String> {
+        let keyword = self.ident()?;
+        let kind = match keyword.as_str() {
+            "class" => TypeKind::Class,
+            "struct" => TypeKind::Struct,
+            "interface" => TypeKind::Interface,
+            "enum" => TypeKind::Enum,
+            "record" if self.is_ident("struct") => {
+                self.pos += 1;
+                TypeKind::RecordStruct
+            }
+            "record" => {
+                if self.is_ident("class") {
+                    self.pos += 1;
+                }
+                TypeKind::Record
+            }
+            other => return Err(self.error(&format!("expected type declaration, found `{other}`"))),
+        };
+        let name = self.ident()?;
+
+        let mut type_params = Vec::new();
+        if self.eat_punct("<") {
+            loop {
+                self.attributes()?;
+                if self.is_ident("in") || self.is_ident("out") {
+                    self.pos += 1;
+                }
+                type_params.push(self.ident()?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(">")?;
+        }
+
+        let primary_parameters = match self.is_punct("(") {
+            true => Some(self.parameters()?),
+            false => None,
+        };
+
+        let header_end = self.pos.checked_sub(1).map_or(0, |i| self.tokens[i].end);
+        let mut base_list_span = header_end..header_end;
+        let mut base_types = Vec::new();
+        if self.is_punct(":") {
+            base_list_span.start = self.tokens[self.pos].start;
+            self.pos += 1;
+            loop {
+                base_types.push(self.type_ref()?);
+                // record base types may pass primary constructor arguments: `: Base(Id)`
+                if self.is_punct("(") {
+                    self.skip_balanced()?;
+                }
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            base_list_span.end = self.tokens[self.pos - 1].end;
+        }
+
+        let mut decl = TypeDecl {
+            kind,
+            name,
+            type_params,
+            primary_parameters,
+            modifiers,
+            attributes,
+            doc,
+            base_types,
+            base_list_span,
+            body_span: None,
+            members: Vec::new(),
+            nested_types: Vec::new(),
+            enum_members: Vec::new(),
+        };
+
+        while !self.is_punct("{") && !self.is_punct(";") {
+            // generic constraints: `where T : class, new()`
+            if self.peek().is_none() {
+                return Ok(decl);
+            }
+            self.pos += 1;
+        }
+
+        if self.eat_punct(";") {
+            return Ok(decl);
+        }
+        let body_start = self.tokens[self.pos].start;
+        self.expect_punct("{")?;
+        if kind == TypeKind::Enum {
+            decl.enum_members = self.enum_members()?;
+        } else if self.members(&mut decl)? {
+            decl.body_span = Some(body_start..self.tokens[self.pos - 1].end);
+        }
+        self.eat_punct(";");
+        Ok(decl)
+    }
+
+    /// Parses members up to and including the closing brace, reporting whether it was found.
+    fn members(&mut self, decl: &mut TypeDecl) -> Result<bool, String> {
+        loop {
+            self.skip_trivia();
+            if self.eat_punct("}") {
+                return Ok(true);
+            }
+            if self.peek().is_none() {
+                return Ok(false);
+            }
+
+            let start = self.peek().map_or(self.src.len(), |t| t.start);
+            let doc = self.doc_comment();
+            let attributes = self.attributes()?;
+            let modifiers = self.modifiers();
+
+            if self.at_type_keyword() {
+                let nested = self.type_decl(doc, attributes, modifiers)?;
+                decl.nested_types.push(nested);
+                continue;
+            }
+            if self.is_ident("event") || self.is_ident("delegate") || self.is_punct("~") {
+                self.skip_member()?;
+                continue;
+            }
+            if self.is_ident("implicit") || self.is_ident("explicit") {
+                self.skip_member()?;
+                continue;
+            }
+            if self.is_ident(&decl.name) && self.is_punct_at(1, "(") {
+                self.pos += 1;
+                let parameters = self.parameters()?;
+                // `: base(...)` / `: this(...)` initializers are skipped with the body
+                self.skip_member()?;
+                decl.members
+                    .push(Member::Constructor(Constructor { parameters }));
+                continue;
+            }
+
+            let nullable_annotations = self.nullable_context.get(self.pos).copied().flatten();
+            let ty = self.type_ref()?;
+
+            if self.is_ident("operator") || self.is_ident("this") {
+                self.skip_member()?;
+                continue;
+            }
+
+            let name = self.qualified_name()?;
+
+            if self.is_punct("<") || self.is_punct("(") {
+                self.skip_method_type_params()?;
+                self.parameters()?;
+                self.skip_member()?;
+                decl.members.push(Member::Method(Method {
+                    name,
+                    span: start..self.tokens[self.pos - 1].end,
+                }));
+                continue;
+            }
+
+            if self.eat_punct("{") {
+                self.skip_accessors()?;
+                let initializer = match self.eat_punct("=") {
+                    true => Some(self.expression_until(&[";"])?),
+                    false => None,
+                };
+                self.eat_punct(";");
+                decl.members.push(Member::Property(Property {
+                    name,
+                    ty,
+                    modifiers,
+                    attributes,
+                    doc,
+                    initializer,
+                    nullable_annotations,
+                }));
+                continue;
+            }
+
+            if self.eat_punct("=>") {
+                self.expression_until(&[";"])?;
+                self.expect_punct(";")?;
+                decl.members.push(Member::Property(Property {
+                    name,
+                    ty,
+                    modifiers,
+                    attributes,
+                    doc,
+                    initializer: None,
+                    nullable_annotations,
+                }));
+                continue;
+            }
+
+            // one or more fields, which aren't modeled: `int a = 1, b;`
+            loop {
+                if self.eat_punct("=") {
+                    self.expression_until(&[";", ","])?;
+                }
+                if !self.eat_punct(",") {
+                    break;
+                }
+                self.ident()?;
+            }
+            self.expect_punct(";")?;
+        }
+    }
+
+    /// Parses a parenthesized parameter list, including the parentheses.
+    fn parameters(&mut self) -> Result<Vec<Parameter>, String> {
+        self.expect_punct("(")?;
+        let mut parameters = Vec::new();
+        while !self.eat_punct(")") {
+            let attributes = self.attributes()?;
+            while PARAMETER_MODIFIERS.iter().any(|m| self.is_ident(m)) && self.is_identifier_at(1) {
+                self.pos += 1;
+            }
+            let nullable_annotations = self.nullable_context.get(self.pos).copied().flatten();
+            let ty = self.type_ref()?;
+            let name = self.ident()?;
+            if self.eat_punct("=") {
+                self.expression_until(&[",", ")"])?;
+            }
+            parameters.push(Parameter {
+                name,
+                ty,
+                attributes,
+                nullable_annotations,
+            });
+            if !self.eat_punct(",") && !self.is_punct(")") {
+                return Err(self.error("expected `,` or `)` in parameter list"));
+            }
+        }
+        Ok(parameters)
+    }
+
+    fn skip_method_type_params(&mut self) -> Result<(), String> {
+        if self.eat_punct("<") {
+            loop {
+                self.attributes()?;
+                self.ident()?;
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(">")?;
+        }
+        Ok(())
+    }
+
+    /// Parses `get; set; }` after the opening brace of a property.
+    fn skip_accessors(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_trivia();
+            if self.eat_punct("}") {
+                return Ok(());
+            }
+            self.attributes()?;
+            self.modifiers();
+            self.ident()?;
+            if self.is_punct("{") {
+                self.skip_balanced()?;
+            } else if self.eat_punct("=>") {
+                self.expression_until(&[";"])?;
+                self.expect_punct(";")?;
+            } else {
+                self.expect_punct(";")?;
+            }
+        }
+    }
+
+    /// Skips an entire member declaration: through its body, or its terminating `;`.
+    fn skip_member(&mut self) -> Result<(), String> {
+        loop {
+            if self.is_punct("{") {
+                self.skip_balanced()?;
+                self.eat_punct(";");
+                return Ok(());
+            }
+            if self.eat_punct(";") {
+                return Ok(());
+            }
+            if self.is_punct("(") || self.is_punct("[") {
+                self.skip_balanced()?;
+                continue;
+            }
+            if self.eat_punct("=>") {
+                self.expression_until(&[";"])?;
+                self.expect_punct(";")?;
+                return Ok(());
+            }
+            self.bump()?;
+        }
+    }
+
+    fn skip_statement(&mut self) -> Result<(), String> {
+        self.expression_until(&[";"])?;
+        if !self.eat_punct(";") {
+            self.skip_balanced()?;
+        }
+        Ok(())
+    }
+
+    /// Consumes tokens up to (not including) one of `terminators` at bracket depth zero
+    /// and returns the consumed source text. A `{` block at depth zero also ends it.
+    fn expression_until(&mut self, terminators: &[&str]) -> Result<String, String> {
+        let start = self.peek().map(|t| t.start).unwrap_or(self.src.len());
+        let mut end = start;
+        loop {
+            if self.peek().is_none() {
+                return Err("unexpected end of file in expression".to_string());
+            }
+            if terminators.iter().any(|t| self.is_punct(t)) || self.is_punct("}") {
+                break;
+            }
+            if self.is_punct("(") || self.is_punct("[") || self.is_punct("{") {
+                self.skip_balanced()?;
+            } else if self.is_punct("<") && self.generic_args_end().is_some() {
+                self.pos = self.generic_args_end().unwrap();
+            } else {
+                self.pos += 1;
+            }
+            end = self.tokens[self.pos - 1].end;
+        }
+        Ok(self.src[start..end].trim().to_string())
+    }
+
+    /// If the current `<` opens a generic argument list, returns the index just past its `>`.
+    fn generic_args_end(&self) -> Option<usize> {
+        let mut depth = 0;
+        for (index, token) in self.tokens.iter().enumerate().skip(self.pos) {
+            match &token.kind {
+                TokenKind::Punct(p) if p == "<" => depth += 1,
+                TokenKind::Punct(p) if p == ">" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(index + 1);
+                    }
+                }
+                TokenKind::Punct(p)
+                    if [",", ".", "?", "[", "]", "(", ")", "::"].contains(&p.as_str()) => {}
+                TokenKind::Identifier(_) => {}
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    fn attributes(&mut self) -> Result<Vec<Attribute>, String> {
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_trivia();
+            if !self.eat_punct("[") {
+                return Ok(attributes);
+            }
+            // attribute targets: `[field: JsonIgnore]`, `[assembly: ...]`
+            if self.is_punct_at(1, ":") {
+                self.pos += 2;
+            }
+            loop {
+                let name = self.qualified_name()?;
+                let mut args = Vec::new();
+                if self.eat_punct("(") {
+                    while !self.eat_punct(")") {
+                        let arg_name = match self.is_punct_at(1, "=") || self.is_punct_at(1, ":") {
+                            true if self.is_identifier() => {
+                                let n = self.ident()?;
+                                self.pos += 1;
+                                Some(n)
+                            }
+                            _ => None,
+                        };
+                        let value = self.expression_until(&[",", ")"])?;
+                        args.push(AttributeArg {
+                            name: arg_name,
+                            value,
+                        });
+                        self.eat_punct(",");
+                    }
+                }
+                attributes.push(Attribute { name, args });
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct("]")?;
+        }
+    }
+
+    fn modifiers(&mut self) -> Vec<String> {
+        let mut modifiers = Vec::new();
+        while let Some(TokenKind::Identifier(word)) = self.peek().map(|t| &t.kind) {
+            // `new()` is an expression, not the `new` modifier
+            if !MODIFIERS.contains(&word.as_str()) || (word == "new" && self.is_punct_at(1, "(")) {
+                break;
+            }
+            modifiers.push(word.clone());
+            self.pos += 1;
+        }
+        modifiers
+    }
+
+    fn type_ref(&mut self) -> Result<TypeRef, String> {
+        let mut ty = if self.eat_punct("(") {
+            let mut items = Vec::new();
+            loop {
+                items.push(self.type_ref()?);
+                // optional element name: `(int Id, string Name)`
+                if self.is_identifier() {
+                    self.pos += 1;
+                }
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(")")?;
+            TypeRef::Tuple(items)
+        } else {
+            let name = self.qualified_name()?;
+            let mut args = Vec::new();
+            if self.is_punct("<") && self.generic_args_end().is_some() {
+                self.pos += 1;
+                // unbound generic names, `List<>` and `Dictionary<,>`, have no arguments
+                if self.is_punct(">") || self.is_punct(",") {
+                    while self.eat_punct(",") {}
+                } else {
+                    loop {
+                        args.push(self.type_ref()?);
+                        if !self.eat_punct(",") {
+                            break;
+                        }
+                    }
+                }
+                self.expect_punct(">")?;
+            }
+            TypeRef::Named { name, args }
+        };
+
+        loop {
+            if self.eat_punct("?") {
+                ty = TypeRef::Nullable(Box::new(ty));
+            } else if self.is_punct("[") && (self.is_punct_at(1, "]") || self.is_punct_at(1, ",")) {
+                self.skip_balanced()?;
+                ty = TypeRef::Array(Box::new(ty));
+            } else if self.eat_punct("*") {
+                // pointer types only appear in unsafe code; treat them as the pointee
+            } else {
+                return Ok(ty);
+            }
+        }
+    }
+
+    fn qualified_name(&mut self) -> Result<String, String> {
+        let mut name = self.ident()?;
+        while (self.is_punct(".") || self.is_punct("::")) && self.is_identifier_at(1) {
+            let separator = self.bump()?;
+            let TokenKind::Punct(separator) = separator.kind else {
+                unreachable!()
+            };
+            name.push_str(&separator);
+            name.push_str(&self.ident()?);
+        }
+        Ok(name)
+    }
+
+    fn at_type_keyword(&self) -> bool {
+        ["class", "struct", "interface", "enum"]
+            .iter()
+            .any(|k| self.is_ident(k))
+            || (self.is_ident("record") && self.is_identifier_at(1))
+    }
+
+    /// The `///` lines just before the current token, which trivia skipping has passed over.
+    fn doc_comment(&self) -> Vec<String> {
+        let mut doc = Vec::new();
+        for token in self.tokens[..self.pos].iter().rev() {
+            match &token.kind {
+                TokenKind::DocComment(line) => doc.push(line.clone()),
+                TokenKind::Directive(_) => {}
+                _ => break,
+            }
+        }
+        doc.reverse();
+        doc
+    }
+
+    /// Skips doc comments and preprocessor directives.
+    fn skip_trivia(&mut self) {
+        while let Some(TokenKind::DocComment(_) | TokenKind::Directive(_)) =
+            self.peek().map(|t| &t.kind)
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips a bracketed group starting at the current `(`, `[` or `{`.
+    fn skip_balanced(&mut self) -> Result<(), String> {
+        let open = self.bump()?;
+        let mut depth = 1;
+        while depth > 0 {
+            let token = self
+                .bump()
+                .map_err(|_| format!("unclosed bracket opened on line {}", open.line))?;
+            if let TokenKind::Punct(p) = &token.kind {
+                match p.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => depth -= 1,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `A, B = 2, C = A | B }` after the opening brace of an enum.
+    fn enum_members(&mut self) -> Result<Vec<EnumMember>, String> {
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.eat_punct("}") || self.peek().is_none() {
+                return Ok(members);
+            }
+            let doc = self.doc_comment();
+            let attributes = self.attributes()?;
+            let name = self.ident()?;
+            let value = match self.eat_punct("=") {
+                true => Some(self.expression_until(&[","])?),
+                false => None,
+            };
+            members.push(EnumMember {
+                name,
+                value,
+                attributes,
+                doc,
+            });
+            self.eat_punct(",");
+        }
+    }
+
+    fn skip_past(&mut self, punct: &str) -> Result<(), String> {
+        while !self.eat_punct(punct) {
+            self.bump()?;
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of file".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Identifier(word)) => {
+                let word = word.clone();
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    fn is_identifier(&self) -> bool {
+        self.is_identifier_at(0)
+    }
+
+    fn is_identifier_at(&self, offset: usize) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Identifier(_))
+        )
+    }
+
+    fn is_ident(&self, word: &str) -> bool {
+        self.is_ident_at(0, word)
+    }
+
+    fn is_ident_at(&self, offset: usize, word: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Identifier(w)) if w == word
+        )
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        self.is_punct_at(0, punct)
+    }
+
+    fn is_punct_at(&self, offset: usize, punct: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Punct(p)) if p == punct
+        )
+    }
+
+    fn eat_ident(&mut self, word: &str) -> bool {
+        if self.is_ident(word) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_ident(&mut self, word: &str) -> Result<(), String> {
+        match self.eat_ident(word) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{word}`"))),
+        }
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if self.is_punct(punct) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), String> {
+        match self.eat_punct(punct) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{punct}`"))),
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        match self.peek() {
+            Some(token) => format!(
+                "{message} on line {}, found `{}`",
+                token.line,
+                &self.src[token.start..token.end]
+            ),
+            None => format!("{message}, found end of file"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property_summary(decl: &TypeDecl) -> Vec<String> {
+        decl.properties()
+            .map(|p| format!("{} {}", p.ty, p.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_file_scoped_namespace_and_class() {
+        let file = parse_csharp(
+            r#"
+            using System;
+            namespace Acme.Api.Dtos;
+
+            public class CustomerDto
+            {
+                public int Id { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(file.namespace.as_deref(), Some("Acme.Api.Dtos"));
+        assert_eq!(file.types.len(), 1);
+        assert_eq!(file.types[0].name, "CustomerDto");
+        assert_eq!(file.types[0].kind, TypeKind::Class);
+        assert_eq!(property_summary(&file.types[0]), vec!["int Id"]);
+    }
+
+    #[test]
+    fn test_block_namespace() {
+        let file = parse_csharp(
+            "namespace Acme { namespace Dtos { public class A { } } public class B { } }",
+        )
+        .unwrap();
+        assert_eq!(file.namespace.as_deref(), Some("Acme"));
+        let names: Vec<&str> = file.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_attributes_comments_and_initializers() {
+        let file = parse_csharp(
+            r#"
+            [Serializable]
+            public class OrderDto // trailing { comment
+            {
+                /// <summary>The id</summary>
+                [JsonPropertyName("order_id"), Required]
+                public int Id { get; set; }
+
+                /* public string Commented { get; set; } */
+                public List<string> Tags { get; set; } = new();
+
+                [Range(1, 10, ErrorMessage = "Out of range")]
+                public decimal? Total { get; private set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let dto = &file.types[0];
+        assert_eq!(dto.attributes[0].name, "Serializable");
+        assert_eq!(
+            property_summary(dto),
+            vec!["int Id", "List<string> Tags", "decimal? Total"]
+        );
+
+        let props: Vec<&Property> = dto.properties().collect();
+        assert_eq!(props[0].attributes.len(), 2);
+        assert_eq!(props[0].attributes[0].args[0].value, "\"order_id\"");
+        assert_eq!(props[1].initializer.as_deref(), Some("new()"));
+        assert_eq!(
+            props[2].attributes[0].args[2],
+            AttributeArg {
+                name: Some("ErrorMessage".to_string()),
+                value: "\"Out of range\"".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_two_properties_on_one_line_and_expression_bodies() {
+        let file = parse_csharp(
+            r#"
+            public class PersonDto {
+                public string First { get; set; } public string Last { get; set; }
+                public string FullName => $"{First} {Last}";
+                public int Age { get => _age; set { _age = value; } }
+                private int _age = 0, _other;
+                public override string ToString() { return FullName; }
+                public PersonDto() { First = "}"; }
+            }
+            "#,
+        )
+        .unwrap();
+        let dto = &file.types[0];
+        assert_eq!(
+            property_summary(dto),
+            vec!["string First", "string Last", "string FullName", "int Age"]
+        );
+    }
+
+    #[test]
+    fn test_generics_nullable_and_arrays() {
+        let file = parse_csharp(
+            r#"
+            public class PagedResult<T> : BaseResult, IEnumerable<T> where T : class, new()
+            {
+                public Dictionary<string, List<T?>>? Groups { get; init; }
+                public int[]? Ids { get; set; }
+                public (int Id, string Name) Pair { get; set; }
+                public System.Guid Key { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let dto = &file.types[0];
+        assert_eq!(dto.type_params, vec!["T"]);
+        let bases: Vec<String> = dto.base_types.iter().map(|t| t.to_string()).collect();
+        assert_eq!(bases, vec!["BaseResult", "IEnumerable<T>"]);
+        assert_eq!(
+            property_summary(dto),
+            vec![
+                "Dictionary<string, List<T?>>? Groups",
+                "int[]? Ids",
+                "(int, string) Pair",
+                "System.Guid Key"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_types_and_enums_are_declarations() {
+        let file = parse_csharp(
+            r#"
+            public class Outer
+            {
+                public enum Status { Active = 1, Inactive = 2 }
+                public class Inner { public int X { get; set; } }
+                public Status State { get; set; }
+            }
+            public record PointDto(int X, int Y);
+            "#,
+        )
+        .unwrap();
+        let outer = &file.types[0];
+        let nested: Vec<(&str, TypeKind)> = outer
+            .nested_types
+            .iter()
+            .map(|t| (t.name.as_str(), t.kind))
+            .collect();
+        assert_eq!(
+            nested,
+            vec![("Status", TypeKind::Enum), ("Inner", TypeKind::Class)]
+        );
+        assert_eq!(property_summary(outer), vec!["Status State"]);
+        let status: Vec<(&str, Option<&str>)> = outer.nested_types[0]
+            .enum_members
+            .iter()
+            .map(|m| (m.name.as_str(), m.value.as_deref()))
+            .collect();
+        assert_eq!(status, vec![("Active", Some("1")), ("Inactive", Some("2"))]);
+        assert_eq!(file.types[1].kind, TypeKind::Record);
+        assert_eq!(file.types[1].name, "PointDto");
+    }
+
+    #[test]
+    fn test_enum_members_with_attributes_and_expressions() {
+        let file = parse_csharp(
+            r#"
+            [Flags]
+            public enum Permissions : byte
+            {
+                None = 0,
+                /// <summary>Can read</summary>
+                [EnumMember(Value = "read")]
+                Read = 1 << 0,
+                Write = 1 << 1,
+                ReadWrite = Read | Write,
+                Admin,
+            }
+            "#,
+        )
+        .unwrap();
+        let permissions = &file.types[0];
+        assert_eq!(permissions.attributes[0].name, "Flags");
+        assert_eq!(permissions.base_types[0].to_string(), "byte");
+        let members: Vec<(&str, Option<&str>)> = permissions
+            .enum_members
+            .iter()
+            .map(|m| (m.name.as_str(), m.value.as_deref()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                ("None", Some("0")),
+                ("Read", Some("1 << 0")),
+                ("Write", Some("1 << 1")),
+                ("ReadWrite", Some("Read | Write")),
+                ("Admin", None),
+            ]
+        );
+        assert_eq!(permissions.enum_members[1].attributes[0].name, "EnumMember");
+    }
+
+    #[test]
+    fn test_directives_between_members() {
+        let file = parse_csharp(
+            r#"
+            #nullable enable
+            public class A
+            {
+            #if DEBUG
+                public string Debug { get; set; }
+            #endif
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(property_summary(&file.types[0]), vec!["string Debug"]);
+    }
+
+    #[test]
+    fn test_nullable_directives_set_annotation_context() {
+        let file = parse_csharp(
+            r#"
+            public record A(string First)
+            {
+            #nullable disable
+                public string Second { get; set; }
+            #nullable enable warnings
+                public string Third { get; set; }
+            #nullable enable
+                public string Fourth => "";
+            #nullable restore
+                public string Fifth { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let contexts: Vec<Option<bool>> = file.types[0]
+            .all_properties()
+            .iter()
+            .map(|p| p.nullable_annotations)
+            .collect();
+        assert_eq!(
+            contexts,
+            vec![None, Some(false), Some(false), Some(true), None]
+        );
+    }
+
+    #[test]
+    fn test_doc_comments_attach_to_declarations() {
+        let file = parse_csharp(
+            r#"
+            /// <summary>
+            /// A customer.
+            /// </summary>
+            [Serializable]
+            public class CustomerDto
+            {
+                /// <summary>Display name.</summary>
+                #if DEBUG
+                public string Name { get; set; }
+                #endif
+                public int Age { get; set; }
+            }
+
+            /// <summary>Status.</summary>
+            public enum Status
+            {
+                /// <summary>Active.</summary>
+                Active,
+                Inactive,
+            }
+            "#,
+        )
+        .unwrap();
+        let customer = &file.types[0];
+        assert_eq!(customer.doc, vec!["<summary>", "A customer.", "</summary>"]);
+        let docs: Vec<&Vec<String>> = customer.properties().map(|p| &p.doc).collect();
+        assert_eq!(
+            docs,
+            vec![
+                &vec!["<summary>Display name.</summary>".to_string()],
+                &vec![]
+            ]
+        );
+        let status = &file.types[1];
+        assert_eq!(status.doc, vec!["<summary>Status.</summary>"]);
+        assert_eq!(
+            status.enum_members[0].doc,
+            vec!["<summary>Active.</summary>"]
+        );
+        assert!(status.enum_members[1].doc.is_empty());
+    }
+
+    #[test]
+    fn test_truncated_class_keeps_parsed_members() {
+        let file = parse_csharp("public class A : B<C> { public int X { get; set; }").unwrap();
+        assert_eq!(file.types[0].base_types[0].to_string(), "B<C>");
+        assert_eq!(property_summary(&file.types[0]), vec!["int X"]);
+        assert!(file.types[0].body_span.is_none());
+        // a member cut off part way is still an error
+        assert!(parse_csharp("public class A { public int X { get; set; } public").is_err());
+    }
+
+    #[test]
+    fn test_usings_constructors_and_methods() {
+        let file = parse_csharp(
+            r#"
+            global using System;
+            using static System.Math;
+            using Acme.Core;
+
+            namespace Acme.Services
+            {
+                public class CustomerService(ILogger<CustomerService> logger) : ICustomerService
+                {
+                    public CustomerService(ICustomerRepository repository, IUnitOfWork unitOfWork)
+                        : this(null) { }
+
+                    public async Task<CustomerDto?> GetAsync<TKey>(TKey id, CancellationToken token = default)
+                        where TKey : struct
+                    {
+                        return await repository.GetAsync(id, token);
+                    }
+
+                    private static bool IsValid(this string value, params int[] codes) => true;
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.usings,
+            vec!["System", "static System.Math", "Acme.Core"]
+        );
+        let service = &file.types[0];
+
+        let primary: Vec<String> = service
+            .primary_parameters
+            .iter()
+            .flatten()
+            .map(|p| format!("{} {}", p.ty, p.name))
+            .collect();
+        assert_eq!(primary, vec!["ILogger<CustomerService> logger"]);
+
+        let constructor = service.constructors().next().unwrap();
+        let constructor_params: Vec<String> = constructor
+            .parameters
+            .iter()
+            .map(|p| p.ty.to_string())
+            .collect();
+        assert_eq!(
+            constructor_params,
+            vec!["ICustomerRepository", "IUnitOfWork"]
+        );
+
+        let methods: Vec<&Method> = service.methods().collect();
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[0].name, "GetAsync");
+        assert_eq!(methods[1].name, "IsValid");
+    }
+
+    #[test]
+    fn test_source_spans() {
+        let source = r#"
+internal class CustomerRepository : BaseCrudRepository<Customer>, ICustomerRepository
+{
+    [Obsolete]
+    protected override IQueryable<Customer> Apply(IQueryable<Customer> query) => query;
+}
+public interface ICustomerRepository
+{
+}
+public class Truncated : Base
+{"#;
+        let file = parse_csharp(source).unwrap();
+        let span = |range: std::ops::Range<usize>| &source[range];
+
+        let repository = &file.types[0];
+        assert_eq!(
+            span(repository.base_list_span.clone()),
+            ": BaseCrudRepository<Customer>, ICustomerRepository"
+        );
+        let body = span(repository.body_span.clone().unwrap());
+        assert!(body.starts_with('{') && body.ends_with('}'));
+        assert_eq!(
+            span(repository.methods().next().unwrap().span.clone()),
+            "[Obsolete]\n    protected override IQueryable<Customer> Apply(IQueryable<Customer> query) => query;"
+        );
+
+        let interface = &file.types[1];
+        assert!(interface.base_list_span.is_empty());
+        assert!(source[..interface.base_list_span.start].ends_with("interface ICustomerRepository"));
+        assert_eq!(file.types[2].body_span, None);
+    }
+}
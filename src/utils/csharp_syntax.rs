@@ -0,0 +1,379 @@
+use std::{fmt, ops::Range};
+
+/// Lightweight syntax model of a C# source file. Only declarations are modeled;
+/// method bodies and initializer expressions are kept as raw source text.
+#[derive(Clone, Debug, Default)]
+pub struct CsFile {
+    /// Using directives without the `using` keyword, e.g. `System.Linq` or `static System.Math`.
+    pub usings: Vec<String>,
+    pub namespace: Option<String>,
+    pub types: Vec<TypeDecl>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TypeKind {
+    Class,
+    Struct,
+    Interface,
+    Enum,
+    Record,
+    RecordStruct,
+}
+
+#[derive(Clone, Debug)]
+pub struct TypeDecl {
+    pub kind: TypeKind,
+    pub name: String,
+    pub type_params: Vec<String>,
+    /// Primary constructor parameters: `record Foo(int Id)` or `class Foo(IService service)`.
+    pub primary_parameters: Option<Vec<Parameter>>,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    /// Lines of the `///` comment before the declaration.
+    pub doc: Vec<String>,
+    pub base_types: Vec<TypeRef>,
+    /// Byte range of the base list from its `:`, or an empty range where one would go.
+    pub base_list_span: Range<usize>,
+    /// Byte range of the `{ ... }` body; `None` when the declaration ends in `;` or is truncated.
+    pub body_span: Option<Range<usize>>,
+    pub members: Vec<Member>,
+    pub nested_types: Vec<TypeDecl>,
+    /// Members of an `enum`; empty for every other kind.
+    pub enum_members: Vec<EnumMember>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Member {
+    Property(Property),
+    Constructor(Constructor),
+    Method(Method),
+}
+
+#[derive(Clone, Debug)]
+pub struct Property {
+    pub name: String,
+    pub ty: TypeRef,
+    pub modifiers: Vec<String>,
+    pub attributes: Vec<Attribute>,
+    /// Lines of the `///` comment before the declaration.
+    pub doc: Vec<String>,
+    /// Raw initializer expression, e.g. `new()` for `= new();`.
+    pub initializer: Option<String>,
+    /// Nullable annotation context at the declaration: set by a `#nullable` directive,
+    /// `None` where the project-wide setting applies.
+    pub nullable_annotations: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Constructor {
+    pub parameters: Vec<Parameter>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Method {
+    pub name: String,
+    /// Byte range from the first attribute or modifier through the body or `;`.
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pub name: String,
+    pub ty: TypeRef,
+    pub attributes: Vec<Attribute>,
+    /// See [`Property::nullable_annotations`].
+    pub nullable_annotations: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnumMember {
+    pub name: String,
+    /// Raw value expression, e.g. `1 << 2` or `Read | Write`.
+    pub value: Option<String>,
+    pub attributes: Vec<Attribute>,
+    /// Lines of the `///` comment before the member.
+    pub doc: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    /// Name as written, e.g. `JsonPropertyName` or `System.Text.Json.Serialization.JsonIgnore`.
+    pub name: String,
+    pub args: Vec<AttributeArg>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeArg {
+    /// Set for named arguments such as `ErrorMessage = "..."`.
+    pub name: Option<String>,
+    /// Raw argument expression as written in the source.
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeRef {
+    /// A possibly qualified, possibly generic type name such as `int` or `List<string>`.
+    Named {
+        name: String,
+        args: Vec<TypeRef>,
+    },
+    Array(Box<TypeRef>),
+    /// A type followed by the `?` nullable marker.
+    Nullable(Box<TypeRef>),
+    Tuple(Vec<TypeRef>),
+}
+
+impl TypeDecl {
+    pub fn properties(&self) -> impl Iterator<Item = &Property> {
+        self.members.iter().filter_map(|member| match member {
+            Member::Property(property) => Some(property),
+            _ => None,
+        })
+    }
+
+    pub fn constructors(&self) -> impl Iterator<Item = &Constructor> {
+        self.members.iter().filter_map(|member| match member {
+            Member::Constructor(constructor) => Some(constructor),
+            _ => None,
+        })
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &Method> {
+        self.members.iter().filter_map(|member| match member {
+            Member::Method(method) => Some(method),
+            _ => None,
+        })
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "public")
+    }
+
+    pub fn is_record(&self) -> bool {
+        matches!(self.kind, TypeKind::Record | TypeKind::RecordStruct)
+    }
+
+    /// Declared properties plus, for positional records, the `{ get; init; }` properties
+    /// the compiler generates from the primary constructor. A parameter whose property
+    /// is declared explicitly in the body is not duplicated.
+    pub fn all_properties(&self) -> Vec<Property> {
+        let mut properties: Vec<Property> = Vec::new();
+        if self.is_record() {
+            for parameter in self.primary_parameters.iter().flatten() {
+                if self.properties().any(|p| p.name == parameter.name) {
+                    continue;
+                }
+                properties.push(Property {
+                    name: parameter.name.clone(),
+                    ty: parameter.ty.clone(),
+                    modifiers: vec!["public".to_string()],
+                    attributes: parameter.attributes.clone(),
+                    // documented by a `<param>` tag on the record
+                    doc: Vec::new(),
+                    initializer: None,
+                    nullable_annotations: parameter.nullable_annotations,
+                });
+            }
+        }
+        properties.extend(self.properties().cloned());
+        properties
+    }
+}
+
+impl Property {
+    pub fn is_public(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "public")
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "static")
+    }
+
+    /// The C# 11 `required` modifier: callers must set the property when constructing it.
+    pub fn is_required(&self) -> bool {
+        self.modifiers.iter().any(|m| m == "required")
+    }
+}
+
+impl Attribute {
+    /// The first positional argument as an unquoted string, e.g. `id` for `[JsonPropertyName("id")]`.
+    pub fn string_arg(&self) -> Option<String> {
+        let arg = self.args.iter().find(|a| a.name.is_none())?;
+        unquote(&arg.value)
+    }
+
+    /// A named argument as an unquoted string, e.g. `read` for `[EnumMember(Value = "read")]`.
+    pub fn named_string_arg(&self, name: &str) -> Option<String> {
+        let arg = self.args.iter().find(|a| a.name.as_deref() == Some(name))?;
+        unquote(&arg.value)
+    }
+
+    /// Matches `Required`, `RequiredAttribute` and namespace-qualified forms of either.
+    pub fn is(&self, name: &str) -> bool {
+        let short = self.name.rsplit('.').next().unwrap_or(&self.name);
+        short == name || short.strip_suffix("Attribute") == Some(name)
+    }
+}
+
+/// Strips the quotes from a regular or verbatim string literal.
+fn unquote(literal: &str) -> Option<String> {
+    let (body, verbatim) = match literal.strip_prefix('@') {
+        Some(rest) => (rest, true),
+        None => (literal, false),
+    };
+    let body = body.strip_prefix('"')?.strip_suffix('"')?;
+    Some(match verbatim {
+        true => body.replace("\"\"", "\""),
+        false => body.replace("\\\"", "\"").replace("\\\\", "\\"),
+    })
+}
+
+impl CsFile {
+    /// Every type declared in the file, nested types included, outer types first.
+    pub fn all_types(&self) -> Vec<&TypeDecl> {
+        fn collect<'a>(decl: &'a TypeDecl, out: &mut Vec<&'a TypeDecl>) {
+            out.push(decl);
+            for nested in &decl.nested_types {
+                collect(nested, out);
+            }
+        }
+        let mut out = Vec::new();
+        for decl in &self.types {
+            collect(decl, &mut out);
+        }
+        out
+    }
+}
+
+impl TypeRef {
+    /// The type name without namespace qualification or generic arguments.
+    pub fn simple_name(&self) -> Option<&str> {
+        match self {
+            TypeRef::Named { name, .. } => name.rsplit(['.', ':']).next(),
+            _ => None,
+        }
+    }
+
+    pub fn generic_args(&self) -> &[TypeRef] {
+        match self {
+            TypeRef::Named { args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    /// Strips a trailing nullable marker or a `Nullable<T>` wrapper, reporting whether one was present.
+    pub fn strip_nullable(&self) -> (&TypeRef, bool) {
+        match self {
+            TypeRef::Nullable(inner) => (inner, true),
+            TypeRef::Named { args, .. }
+                if args.len() == 1 && self.simple_name() == Some("Nullable") =>
+            {
+                (&args[0], true)
+            }
+            other => (other, false),
+        }
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeRef::Named { name, args } if args.is_empty() => write!(f, "{name}"),
+            TypeRef::Named { name, args } => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{name}<{}>", args.join(", "))
+            }
+            TypeRef::Array(inner) => write!(f, "{inner}[]"),
+            TypeRef::Nullable(inner) => write!(f, "{inner}?"),
+            TypeRef::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|a| a.to_string()).collect();
+                write!(f, "({})", items.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::csharp_parser::parse_csharp;
+
+    fn named(name: &str, args: Vec<TypeRef>) -> TypeRef {
+        TypeRef::Named {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn test_type_ref_display() {
+        let ty = TypeRef::Nullable(Box::new(named(
+            "Dictionary",
+            vec![
+                named("string", vec![]),
+                TypeRef::Array(Box::new(named("int", vec![]))),
+            ],
+        )));
+        assert_eq!(ty.to_string(), "Dictionary<string, int[]>?");
+    }
+
+    #[test]
+    fn test_positional_record_properties() {
+        let file = parse_csharp(
+            r#"
+            public record CustomerDto([property: JsonPropertyName("id")] int Id, string Name)
+            {
+                public string Name { get; set; } = Name.Trim();
+                public string? Email { get; init; }
+            }
+            "#,
+        )
+        .unwrap();
+        let properties: Vec<String> = file.types[0]
+            .all_properties()
+            .iter()
+            .map(|p| format!("{} {}", p.ty, p.name))
+            .collect();
+        assert_eq!(properties, vec!["int Id", "string Name", "string? Email"]);
+        assert!(file.types[0].all_properties()[0].attributes[0].is("JsonPropertyName"));
+    }
+
+    #[test]
+    fn test_class_primary_constructor_is_not_properties() {
+        let file = parse_csharp("public class Service(ILogger logger) { }").unwrap();
+        assert!(file.types[0].all_properties().is_empty());
+    }
+
+    #[test]
+    fn test_attribute_string_args() {
+        let attribute = Attribute {
+            name: "EnumMember".to_string(),
+            args: vec![
+                AttributeArg {
+                    name: None,
+                    value: r#""say \"hi\"""#.to_string(),
+                },
+                AttributeArg {
+                    name: Some("Value".to_string()),
+                    value: r#"@"c:\dir""#.to_string(),
+                },
+            ],
+        };
+        assert_eq!(attribute.string_arg().as_deref(), Some(r#"say "hi""#));
+        assert_eq!(
+            attribute.named_string_arg("Value").as_deref(),
+            Some(r"c:\dir")
+        );
+        assert_eq!(attribute.named_string_arg("Other"), None);
+    }
+
+    #[test]
+    fn test_attribute_name_matching() {
+        let attribute = Attribute {
+            name: "System.ComponentModel.DataAnnotations.RequiredAttribute".to_string(),
+            args: vec![],
+        };
+        assert!(attribute.is("Required"));
+        assert!(!attribute.is("Range"));
+    }
+}
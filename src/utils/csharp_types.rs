@@ -0,0 +1,111 @@
+use crate::utils::csharp_syntax::{Property, TypeRef};
+
+/// Generic collections serialized as JSON arrays.
+pub const COLLECTION_TYPES: [&str; 19] = [
+    "List",
+    "IList",
+    "ICollection",
+    "IEnumerable",
+    "IReadOnlyList",
+    "IReadOnlyCollection",
+    "Collection",
+    "ReadOnlyCollection",
+    "ObservableCollection",
+    "HashSet",
+    "SortedSet",
+    "ISet",
+    "IReadOnlySet",
+    "LinkedList",
+    "Queue",
+    "Stack",
+    "ImmutableArray",
+    "ImmutableList",
+    "ImmutableHashSet",
+];
+
+/// Generic dictionaries serialized as JSON objects.
+pub const DICTIONARY_TYPES: [&str; 8] = [
+    "Dictionary",
+    "IDictionary",
+    "IReadOnlyDictionary",
+    "ReadOnlyDictionary",
+    "SortedDictionary",
+    "SortedList",
+    "ConcurrentDictionary",
+    "ImmutableDictionary",
+];
+
+/// Value types that can't hold null unless written as `T?`, even where nullable
+/// annotations are disabled.
+pub const VALUE_TYPES: [&str; 33] = [
+    "bool",
+    "Boolean",
+    "byte",
+    "Byte",
+    "sbyte",
+    "SByte",
+    "short",
+    "Int16",
+    "ushort",
+    "UInt16",
+    "int",
+    "Int32",
+    "uint",
+    "UInt32",
+    "long",
+    "Int64",
+    "ulong",
+    "UInt64",
+    "float",
+    "Single",
+    "double",
+    "Double",
+    "decimal",
+    "Decimal",
+    "char",
+    "Char",
+    "Guid",
+    "DateTime",
+    "DateTimeOffset",
+    "DateOnly",
+    "TimeOnly",
+    "TimeSpan",
+    "ValueTuple",
+];
+
+/// Integral types, validated as integers by Zod schemas and JSON Schema.
+pub const INTEGER_TYPES: [&str; 16] = [
+    "byte", "Byte", "sbyte", "SByte", "short", "Int16", "ushort", "UInt16", "int", "Int32", "uint",
+    "UInt32", "long", "Int64", "ulong", "UInt64",
+];
+
+/// Whether a property can hold null: it is written `T?`, or it is a reference type
+/// declared where nullable annotations are disabled. `[Required]` and an initializer
+/// other than `null` rule that out. `annotations_by_default` is the project's setting
+/// for code outside `#nullable` directives; `is_value_type` recognizes structs and
+/// enums beyond the built-in [`VALUE_TYPES`].
+pub fn can_be_null(
+    property: &Property,
+    type_params: &[String],
+    annotations_by_default: bool,
+    is_value_type: impl Fn(&TypeRef) -> bool,
+) -> bool {
+    let (property_type, is_marked_nullable) = property.ty.strip_nullable();
+    let annotations_enabled = property
+        .nullable_annotations
+        .unwrap_or(annotations_by_default);
+    let is_type_param =
+        matches!(property_type.simple_name(), Some(name) if type_params.iter().any(|p| p == name));
+    let is_oblivious_reference = !annotations_enabled
+        && !is_type_param
+        && !is_value_type(property_type)
+        && !matches!(property_type, TypeRef::Tuple(_))
+        && !VALUE_TYPES.contains(&property_type.simple_name().unwrap_or_default());
+    let is_initialized = property
+        .initializer
+        .as_deref()
+        .is_some_and(|value| !matches!(value.trim(), "null" | "default"));
+    (is_marked_nullable || is_oblivious_reference)
+        && !is_initialized
+        && !property.attributes.iter().any(|a| a.is("Required"))
+}
@@ -0,0 +1,196 @@
+use glob::{MatchOptions, Pattern};
+
+use crate::{
+    utils::{
+        csharp_syntax::{CsFile, TypeDecl, TypeKind},
+        csharp_types::VALUE_TYPES,
+    },
+    FieldWithType,
+};
+
+/// Properties of an entity class that query criteria can filter and sort on: public
+/// instance properties of built-in scalar types, `string` and enums, including those
+/// inherited from a base class declared in `file` or `references`. Navigation
+/// properties and collections are left out. `entity_name` picks the class, otherwise
+/// the first public class of `file` is used.
+pub fn entity_fields(
+    file: &CsFile,
+    entity_name: Option<&str>,
+    references: &[CsFile],
+) -> Result<Vec<FieldWithType>, String> {
+    let declarations: Vec<&TypeDecl> = std::iter::once(file)
+        .chain(references)
+        .flat_map(|file| file.all_types())
+        .collect();
+    let entity = match entity_name {
+        Some(name) => file.all_types().into_iter().find(|decl| decl.name == name),
+        None => file.all_types().into_iter().find(|decl| {
+            matches!(decl.kind, TypeKind::Class | TypeKind::Record) && decl.is_public()
+        }),
+    }
+    .ok_or(match entity_name {
+        Some(name) => format!("class {name} not found in entity file"),
+        None => "no public class found in entity file".to_string(),
+    })?;
+
+    let is_enum = |name: &str| {
+        declarations
+            .iter()
+            .any(|decl| decl.kind == TypeKind::Enum && decl.name == name)
+    };
+    let mut fields: Vec<FieldWithType> = Vec::new();
+    let mut class = Some(entity);
+    // base class properties first, as the compiler lays them out
+    let mut hierarchy = Vec::new();
+    while let Some(decl) = class {
+        if hierarchy
+            .iter()
+            .any(|seen: &&TypeDecl| seen.name == decl.name)
+        {
+            break;
+        }
+        hierarchy.push(decl);
+        class = decl
+            .base_types
+            .first()
+            .and_then(|base| base.simple_name())
+            .and_then(|base| {
+                declarations
+                    .iter()
+                    .find(|d| {
+                        matches!(d.kind, TypeKind::Class | TypeKind::Record) && d.name == base
+                    })
+                    .copied()
+            });
+    }
+    for decl in hierarchy.into_iter().rev() {
+        for property in decl
+            .all_properties()
+            .into_iter()
+            .filter(|p| p.is_public() && !p.is_static())
+        {
+            let ty = property.ty.strip_nullable().0;
+            let is_scalar = ty.generic_args().is_empty()
+                && ty.simple_name().is_some_and(|name| {
+                    (VALUE_TYPES.contains(&name) && name != "ValueTuple")
+                        || matches!(name, "string" | "String")
+                        || is_enum(name)
+                });
+            if !is_scalar {
+                continue;
+            }
+            // a property redeclared with `new` replaces the inherited one
+            fields.retain(|field| field.field != property.name);
+            fields.push(FieldWithType {
+                field: property.name.clone(),
+                field_type: ty.to_string(),
+            });
+        }
+    }
+    Ok(fields)
+}
+
+/// Whether a field name matches any `include` pattern (or `include` is empty) and no
+/// `exclude` pattern. Patterns are case-insensitive globs such as `*Date` or `Customer*`.
+pub fn field_filter(
+    include: &[String],
+    exclude: &[String],
+) -> Result<impl Fn(&str) -> bool, String> {
+    let compile = |patterns: &[String]| -> Result<Vec<Pattern>, String> {
+        patterns
+            .iter()
+            .map(|p| Pattern::new(p).map_err(|e| format!("invalid field pattern {p}: {e}")))
+            .collect()
+    };
+    let (include, exclude) = (compile(include)?, compile(exclude)?);
+    let options = MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    Ok(move |name: &str| {
+        (include.is_empty() || include.iter().any(|p| p.matches_with(name, options)))
+            && !exclude.iter().any(|p| p.matches_with(name, options))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::csharp_parser::parse_csharp;
+
+    fn names(fields: &[FieldWithType]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|f| format!("{} {}", f.field_type, f.field))
+            .collect()
+    }
+
+    #[test]
+    fn test_scalar_properties_of_the_entity_and_its_base() {
+        let entity = parse_csharp(
+            r#"
+            public class Customer : AuditedEntity
+            {
+                public int CustomerId { get; set; }
+                public string? LastName { get; set; }
+                public CustomerStatus Status { get; set; }
+                public DateTime? BirthDate { get; set; }
+                public virtual Address Address { get; set; }
+                public ICollection<Order> Orders { get; set; }
+                public List<string> Tags { get; set; }
+                public static int Count { get; set; }
+                private decimal Secret { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let references = vec![parse_csharp(
+            r#"
+            public abstract class AuditedEntity { public DateTime CreatedAt { get; set; } }
+            public enum CustomerStatus { Active, Blocked }
+            "#,
+        )
+        .unwrap()];
+        let fields = entity_fields(&entity, None, &references).unwrap();
+        assert_eq!(
+            names(&fields),
+            vec![
+                "DateTime CreatedAt",
+                "int CustomerId",
+                "string LastName",
+                "CustomerStatus Status",
+                "DateTime BirthDate",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entity_is_picked_by_name() {
+        let file = parse_csharp(
+            "public class Other { public int A { get; set; } } public class Customer { public int B { get; set; } }",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&entity_fields(&file, Some("Customer"), &[]).unwrap()),
+            vec!["int B"]
+        );
+        assert!(entity_fields(&file, Some("Order"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_include_and_exclude_patterns() {
+        let keep = field_filter(
+            &["*name".to_string(), "Status".to_string()],
+            &["Middle*".to_string()],
+        )
+        .unwrap();
+        assert!(keep("LastName"));
+        assert!(keep("status"));
+        assert!(!keep("MiddleName"));
+        assert!(!keep("CustomerId"));
+
+        let keep_all = field_filter(&[], &[]).unwrap();
+        assert!(keep_all("Anything"));
+        assert!(field_filter(&["[".to_string()], &[]).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use crate::utils::{
+    csharp_lexer::{tokenize, TokenKind},
+    csharp_syntax::TypeDecl,
+};
+
+/// Computes the numeric value of every member of an enum declaration, following the
+/// C# rules: members without an initializer are one more than the previous member.
+/// Initializers may use literals, earlier members and the usual integer operators.
+/// A member whose value can't be evaluated is `None`, as is everything implicit after it.
+pub fn enum_values(decl: &TypeDecl) -> Vec<Option<i64>> {
+    let mut values: Vec<(String, Option<i64>)> = Vec::new();
+
+    for member in &decl.enum_members {
+        let value = match &member.value {
+            Some(expression) => evaluate(expression, &values),
+            None => match values.last() {
+                Some((_, previous)) => previous.map(|v| v + 1),
+                None => Some(0),
+            },
+        };
+        values.push((member.name.clone(), value));
+    }
+
+    values.into_iter().map(|(_, value)| value).collect()
+}
+
+fn evaluate(expression: &str, known: &[(String, Option<i64>)]) -> Option<i64> {
+    let tokens: Vec<TokenKind> = tokenize(expression)
+        .ok()?
+        .into_iter()
+        .map(|t| t.kind)
+        .collect();
+    let mut evaluator = Evaluator {
+        tokens,
+        pos: 0,
+        known,
+    };
+    let value = evaluator.binary(0)?;
+    match evaluator.pos == evaluator.tokens.len() {
+        true => Some(value),
+        false => None,
+    }
+}
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: [&[&str]; 6] = [
+    &["|"],
+    &["^"],
+    &["&"],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+const INTEGRAL_TYPES: [&str; 8] = [
+    "sbyte", "byte", "short", "ushort", "int", "uint", "long", "ulong",
+];
+
+struct Evaluator<'a> {
+    tokens: Vec<TokenKind>,
+    pos: usize,
+    known: &'a [(String, Option<i64>)],
+}
+
+impl Evaluator<'_> {
+    fn binary(&mut self, level: usize) -> Option<i64> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = self.operator(PRECEDENCE[level]) {
+            let right = self.binary(level + 1)?;
+            left = match op {
+                "|" => left | right,
+                "^" => left ^ right,
+                "&" => left & right,
+                "<<" => left.checked_shl(u32::try_from(right).ok()?)?,
+                ">>" => left.checked_shr(u32::try_from(right).ok()?)?,
+                "+" => left.checked_add(right)?,
+                "-" => left.checked_sub(right)?,
+                "*" => left.checked_mul(right)?,
+                "/" => left.checked_div(right)?,
+                "%" => left.checked_rem(right)?,
+                _ => unreachable!(),
+            };
+        }
+        Some(left)
+    }
+
+    /// Consumes one of `ops` if it is next. `>>` arrives from the lexer as two `>` tokens.
+    fn operator(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        let op = ops.iter().find(|op| match **op {
+            ">>" => self.is_punct(0, ">") && self.is_punct(1, ">"),
+            op => self.is_punct(0, op),
+        })?;
+        self.pos += if *op == ">>" { 2 } else { 1 };
+        Some(op)
+    }
+
+    fn unary(&mut self) -> Option<i64> {
+        if self.operator(&["-"]).is_some() {
+            return self.unary().map(|v| -v);
+        }
+        if self.operator(&["~"]).is_some() {
+            return self.unary().map(|v| !v);
+        }
+        if self.operator(&["+"]).is_some() {
+            return self.unary();
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Option<i64> {
+        let token = self.tokens.get(self.pos)?.clone();
+        self.pos += 1;
+        match token {
+            TokenKind::Number(literal) => parse_integer(&literal),
+            TokenKind::Punct(p) if p == "(" => {
+                // casts such as `(int)Other.Value` are transparent
+                let is_cast = matches!(
+                    self.tokens.get(self.pos),
+                    Some(TokenKind::Identifier(ty)) if INTEGRAL_TYPES.contains(&ty.as_str())
+                );
+                if is_cast && self.is_punct(1, ")") {
+                    self.pos += 2;
+                    return self.unary();
+                }
+                let value = self.binary(0)?;
+                self.operator(&[")"])?;
+                Some(value)
+            }
+            TokenKind::Identifier(mut name) => {
+                // `Permissions.Read` refers to a member of the same enum
+                while self.is_punct(0, ".") {
+                    self.pos += 1;
+                    match self.tokens.get(self.pos) {
+                        Some(TokenKind::Identifier(next)) => name = next.clone(),
+                        _ => return None,
+                    }
+                    self.pos += 1;
+                }
+                self.known
+                    .iter()
+                    .find(|(known, _)| *known == name)
+                    .and_then(|(_, value)| *value)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, offset: usize, punct: &str) -> bool {
+        matches!(self.tokens.get(self.pos + offset), Some(TokenKind::Punct(p)) if p == punct)
+    }
+}
+
+fn parse_integer(literal: &str) -> Option<i64> {
+    let digits: String = literal
+        .trim_end_matches(['u', 'U', 'l', 'L'])
+        .chars()
+        .filter(|c| *c != '_')
+        .collect();
+    let lower = digits.to_ascii_lowercase();
+    if let Some(hex) = lower.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = lower.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2).ok()
+    } else {
+        digits.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::csharp_parser::parse_csharp;
+
+    fn values(source: &str) -> Vec<Option<i64>> {
+        let file = parse_csharp(source).unwrap();
+        enum_values(&file.types[0])
+    }
+
+    #[test]
+    fn test_implicit_values() {
+        assert_eq!(
+            values("enum Status { Draft, Active, Archived }"),
+            vec![Some(0), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn test_explicit_values_continue_counting() {
+        assert_eq!(
+            values("enum Status { Draft = 10, Active, Archived = -1, Deleted }"),
+            vec![Some(10), Some(11), Some(-1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_flag_expressions() {
+        assert_eq!(
+            values(
+                "[Flags] enum P { None = 0, Read = 1 << 0, Write = 0x2, Delete = 0b100, \
+                 All = (Read) | P.Write | Delete, NotRead = All & ~Read, Big = 1_000 * (int)(2 + 1) }"
+            ),
+            vec![
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(4),
+                Some(7),
+                Some(6),
+                Some(3000)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_references_are_none() {
+        assert_eq!(
+            values("enum E { A = Other.Value, B, C = 5 }"),
+            vec![None, None, Some(5)]
+        );
+    }
+}
@@ -1,55 +1,54 @@
-use regex::Regex;
+use crate::utils::csharp_syntax::{CsFile, TypeDecl, TypeKind};
 
-pub fn get_class_name(file_data: String) -> Option<String> {
-    let class_regex = Regex::new(r"(?m)^\s*public\s+class\s+(\w+)").unwrap();
-
-    if let Some(captures) = class_regex.captures(&file_data) {
-        if let Some(class_name) = captures.get(1) {
-            return Some(class_name.as_str().to_string());
-        }
-    }
-
-    None
+/// The first public class declared in the file.
+fn first_public_class(file: &CsFile) -> Option<&TypeDecl> {
+    file.all_types()
+        .into_iter()
+        .find(|decl| decl.kind == TypeKind::Class && decl.is_public())
 }
 
-pub fn get_class_name_and_line_number(file_data: String) -> Option<(String, usize)> {
-    // Define the regex pattern
-    let class_regex = Regex::new(r"^\s*public\s+class\s+(\w+)").unwrap();
-
-    // Iterate through each line with line numbers
-    for (index, line) in file_data.lines().enumerate() {
-        if let Some(captures) = class_regex.captures(&line) {
-            if let Some(class_name) = captures.get(1) {
-                return Some((class_name.as_str().to_string(), index));
-            }
-        }
-    }
-
-    None
+pub fn get_class_name(file: &CsFile) -> Option<String> {
+    first_public_class(file).map(|decl| decl.name.clone())
 }
 
-pub fn extract_entity_from_base_crud_repo_class(file_data: String) -> Option<String> {
-    let re =
-        Regex::new(r"(?m)^\s*public\s+class\s+\w+\s*:\s*\w+(?:<(\w+)>)?\s*(?:,|\{|$)").unwrap();
-    if let Some(captures) = re.captures(file_data.as_str()) {
-        if let Some(generic_type) = captures.get(1) {
-            return Some(generic_type.as_str().to_string());
-        }
-    }
-
-    None
+/// Returns the first generic argument of the repository class's first base type,
+/// e.g. `Customer` for `CustomerRepository : BaseCrudRepository<Customer>`. The
+/// repository is the class, public or not, deriving from a generic base; one named
+/// `...Repository` is preferred.
+pub fn extract_entity_from_base_crud_repo_class(file: &CsFile) -> Option<String> {
+    let repositories: Vec<&TypeDecl> = file
+        .all_types()
+        .into_iter()
+        .filter(|decl| decl.kind == TypeKind::Class)
+        .filter(|decl| {
+            decl.base_types
+                .first()
+                .is_some_and(|base| !base.generic_args().is_empty())
+        })
+        .collect();
+    let repository = repositories
+        .iter()
+        .find(|decl| decl.name.ends_with("Repository"))
+        .or(repositories.first())?;
+    let base_type = repository.base_types.first()?;
+    base_type.generic_args().first().map(|arg| arg.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::csharp_parser::parse_csharp;
+
+    fn parse(source: &str) -> CsFile {
+        parse_csharp(source).unwrap()
+    }
 
     #[test]
     fn test_get_generic_type() {
         let declaration =
             "public class CustomerRepository : BaseCrudRepository<Customer>, ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             Some("Customer".to_string())
         );
     }
@@ -58,7 +57,7 @@ mod tests {
     fn test_no_generic_type() {
         let declaration = "public class CustomerRepository : ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             None
         );
     }
@@ -67,7 +66,7 @@ mod tests {
     fn test_multiple_generic_types() {
         let declaration = "public class CustomerRepository : BaseCrudRepository<Customer, Order>, ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             Some("Customer".to_string())
         );
         // It will only capture the first generic type
@@ -77,7 +76,7 @@ mod tests {
     fn test_generic_type_with_numbers() {
         let declaration = "public class CustomerRepository : BaseCrudRepository<Customer123>, ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             Some("Customer123".to_string())
         );
     }
@@ -86,7 +85,7 @@ mod tests {
     fn test_generic_type_with_underscores() {
         let declaration = "public class CustomerRepository : BaseCrudRepository<Customer_Type>, ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             Some("Customer_Type".to_string())
         );
     }
@@ -96,7 +95,7 @@ mod tests {
         let declaration =
             "public class CustomerRepository : BaseCrudRepository<>, ICustomerRepository";
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             None
         );
     }
@@ -109,8 +108,46 @@ mod tests {
                 IOrderRepository {
             "#;
         assert_eq!(
-            extract_entity_from_base_crud_repo_class(declaration.to_string()),
+            extract_entity_from_base_crud_repo_class(&parse(declaration)),
             Some("Order".to_string())
         );
     }
+
+    #[test]
+    fn test_internal_repository_class() {
+        let file = parse(
+            r#"namespace Acme.Infrastructure.Repositories;
+
+internal class Cache : Dictionary<string, object> { }
+
+internal class CustomerRepository : BaseCrudRepository<Customer>, ICustomerRepository
+{
+}
+"#,
+        );
+        assert_eq!(
+            extract_entity_from_base_crud_repo_class(&file),
+            Some("Customer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_class_name_skips_non_public_classes() {
+        let file = parse(
+            r#"using System;
+
+namespace Acme.Infrastructure.Repositories;
+
+internal class Helper { }
+
+public class CustomerRepository : BaseCrudRepository<Customer>
+{
+}
+"#,
+        );
+        assert_eq!(
+            get_class_name(&file),
+            Some("CustomerRepository".to_string())
+        );
+    }
 }
@@ -1,14 +1,7 @@
-use regex::Regex;
+use crate::utils::csharp_syntax::CsFile;
 
-pub fn get_base_namespace(file_data: String) -> Option<String> {
-    let re = Regex::new(r"^namespace\s+([a-zA-Z0-9_]+)").unwrap();
-
-    for line in file_data.lines() {
-        if let Some(captures) = re.captures(&line) {
-            if let Some(namespace) = captures.get(1) {
-                return Some(namespace.as_str().to_string());
-            }
-        }
-    }
-    None
+/// The root segment of the file's namespace, e.g. `Acme` for `namespace Acme.Api.Repositories;`.
+pub fn get_base_namespace(file: &CsFile) -> Option<String> {
+    let namespace = file.namespace.as_ref()?;
+    namespace.split('.').next().map(|root| root.to_string())
 }
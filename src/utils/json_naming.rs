@@ -0,0 +1,219 @@
+use crate::utils::csharp_syntax::{Attribute, EnumMember};
+
+/// Property naming policy of the API's JSON serializer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+pub enum NamingPolicy {
+    /// `JsonNamingPolicy.CamelCase`, the ASP.NET Core default: `IOStream` → `ioStream`
+    #[default]
+    Camel,
+    /// Names as declared, when no naming policy is configured
+    Pascal,
+    /// `JsonNamingPolicy.SnakeCaseLower`: `FirstName` → `first_name`
+    Snake,
+    /// `JsonNamingPolicy.KebabCaseLower`: `FirstName` → `first-name`
+    Kebab,
+}
+
+impl NamingPolicy {
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            NamingPolicy::Camel => camel_case(name),
+            NamingPolicy::Pascal => name.to_string(),
+            NamingPolicy::Snake => words(name).join("_"),
+            NamingPolicy::Kebab => words(name).join("-"),
+        }
+    }
+}
+
+/// Whether a member is left out of the JSON by `[JsonIgnore]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JsonIgnore {
+    Never,
+    Always,
+    /// `Condition = WhenWritingNull` or `WhenWritingDefault`: present only sometimes.
+    WhenWriting,
+}
+
+/// Reads System.Text.Json's and Newtonsoft's `[JsonIgnore]`.
+pub fn json_ignore(attributes: &[Attribute]) -> JsonIgnore {
+    let Some(attribute) = attributes.iter().find(|a| a.is("JsonIgnore")) else {
+        return JsonIgnore::Never;
+    };
+    let condition = attribute
+        .args
+        .iter()
+        .find(|arg| arg.name.as_deref() == Some("Condition"))
+        .map(|arg| arg.value.rsplit('.').next().unwrap_or(&arg.value).trim());
+    match condition {
+        None | Some("Always") => JsonIgnore::Always,
+        Some("Never") => JsonIgnore::Never,
+        Some(_) => JsonIgnore::WhenWriting,
+    }
+}
+
+/// The name a member is serialized under: `[JsonPropertyName("...")]` or Newtonsoft's
+/// `[JsonProperty("...")]` verbatim, otherwise the member name under `policy`.
+pub fn serialized_name(name: &str, attributes: &[Attribute], policy: NamingPolicy) -> String {
+    attributes
+        .iter()
+        .find_map(|a| match a.is("JsonPropertyName") {
+            true => a.string_arg(),
+            false if a.is("JsonProperty") => a
+                .string_arg()
+                .or_else(|| a.named_string_arg("PropertyName")),
+            false => None,
+        })
+        .unwrap_or_else(|| policy.apply(name))
+}
+
+/// The string `JsonStringEnumConverter` writes for an enum member: its
+/// `[JsonStringEnumMemberName]` or `[EnumMember(Value = ...)]`, otherwise its name.
+pub fn enum_member_name(member: &EnumMember) -> String {
+    member
+        .attributes
+        .iter()
+        .find_map(|a| match a.is("JsonStringEnumMemberName") {
+            true => a.string_arg(),
+            false if a.is("EnumMember") => a.named_string_arg("Value"),
+            false => None,
+        })
+        .unwrap_or_else(|| member.name.clone())
+}
+
+/// A C# member name for a JSON or TypeScript name: `first-name` → `FirstName`,
+/// `userId` → `UserId`. A leading digit gets an underscore.
+pub fn pascal_case(name: &str) -> String {
+    let pascal: String = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    match pascal.chars().next() {
+        None => "Value".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("_{pascal}"),
+        Some(_) => pascal,
+    }
+}
+
+/// Lowercases the leading run of capitals, keeping the one that starts the next word.
+fn camel_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut camel = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        let next_is_lower = chars.get(i + 1).is_some_and(|next| !next.is_uppercase());
+        if !c.is_uppercase() || (i > 0 && next_is_lower) {
+            camel.extend(chars[i..].iter());
+            break;
+        }
+        camel.extend(c.to_lowercase());
+    }
+    camel
+}
+
+/// Lowercase words of a PascalCase name: `IOStream2Id` → `io`, `stream2`, `id`.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ') {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|p| chars[p]);
+        let starts_word = c.is_uppercase()
+            && match previous {
+                Some(p) if p.is_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_uppercase() => chars.get(i + 1).is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        if starts_word && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::csharp_parser::parse_csharp;
+
+    #[test]
+    fn test_naming_policies() {
+        let cases = [
+            ("FirstName", "firstName", "first_name", "first-name"),
+            ("Id", "id", "id", "id"),
+            ("ID", "id", "id", "id"),
+            ("IOStream", "ioStream", "io_stream", "io-stream"),
+            (
+                "Address2Line",
+                "address2Line",
+                "address2_line",
+                "address2-line",
+            ),
+            (
+                "already_snake",
+                "already_snake",
+                "already_snake",
+                "already-snake",
+            ),
+        ];
+        for (name, camel, snake, kebab) in cases {
+            assert_eq!(NamingPolicy::Camel.apply(name), camel);
+            assert_eq!(NamingPolicy::Pascal.apply(name), name);
+            assert_eq!(NamingPolicy::Snake.apply(name), snake);
+            assert_eq!(NamingPolicy::Kebab.apply(name), kebab);
+        }
+    }
+
+    #[test]
+    fn test_serialization_attributes() {
+        let file = parse_csharp(
+            r#"
+            public class Dto
+            {
+                [JsonPropertyName("customer_id")] public int CustomerId { get; set; }
+                [JsonProperty("Full Name")] public string Name { get; set; }
+                [JsonProperty(PropertyName = "mail")] public string Email { get; set; }
+                [JsonIgnore] public string Secret { get; set; }
+                [JsonIgnore(Condition = JsonIgnoreCondition.WhenWritingNull)] public string? Note { get; set; }
+                [JsonIgnore(Condition = JsonIgnoreCondition.Never)] public int Version { get; set; }
+            }
+            "#,
+        )
+        .unwrap();
+        let properties: Vec<(String, JsonIgnore)> = file.types[0]
+            .properties()
+            .map(|p| {
+                (
+                    serialized_name(&p.name, &p.attributes, NamingPolicy::Snake),
+                    json_ignore(&p.attributes),
+                )
+            })
+            .collect();
+        assert_eq!(
+            properties,
+            vec![
+                ("customer_id".to_string(), JsonIgnore::Never),
+                ("Full Name".to_string(), JsonIgnore::Never),
+                ("mail".to_string(), JsonIgnore::Never),
+                ("secret".to_string(), JsonIgnore::Always),
+                ("note".to_string(), JsonIgnore::WhenWriting),
+                ("version".to_string(), JsonIgnore::Never),
+            ]
+        );
+    }
+}
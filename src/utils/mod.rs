@@ -1,5 +1,16 @@
+pub mod csharp_lexer;
+pub mod csharp_parser;
+pub mod csharp_syntax;
+pub mod csharp_types;
+pub mod entity_fields;
+pub mod enum_values;
 pub mod get_class_name;
 pub mod get_constructor_interfaces;
-pub mod get_interfaces;
 pub mod get_namespace;
 pub mod interface_to_name_transform;
+pub mod json_naming;
+pub mod ts_parser;
+pub mod ts_syntax;
+pub mod ts_type_map;
+pub mod type_scopes;
+pub mod xml_doc;
@@ -0,0 +1,824 @@
+use crate::utils::ts_syntax::{
+    TsDeclaration, TsDeclarationKind, TsEnumMember, TsFile, TsIndexSignature, TsMember, TsType,
+};
+
+/// Parses the `interface`, `type` and `enum` declarations of TypeScript source into a
+/// [`TsFile`]. Other statements are skipped, so a whole module can be passed in.
+pub fn parse_typescript(source: &str) -> Result<TsFile, String> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+    };
+    let mut file = TsFile::default();
+    let mut doc = None;
+    while let Some(token) = parser.peek() {
+        match &token.kind {
+            TokenKind::DocComment(text) => {
+                doc = Some(text.clone());
+                parser.pos += 1;
+                continue;
+            }
+            // modifiers keep the comment for the declaration they introduce
+            TokenKind::Identifier(word) if matches!(word.as_str(), "export" | "declare") => {
+                parser.pos += 1;
+                continue;
+            }
+            TokenKind::Identifier(word) if word == "interface" && parser.is_identifier_at(1) => {
+                parser.pos += 1;
+                file.declarations.push(parser.interface(doc.take())?);
+            }
+            TokenKind::Identifier(word)
+                if word == "type"
+                    && parser.is_identifier_at(1)
+                    && (parser.is_punct_at(2, "=") || parser.is_punct_at(2, "<")) =>
+            {
+                parser.pos += 1;
+                file.declarations.push(parser.alias(doc.take())?);
+            }
+            TokenKind::Identifier(word) if word == "enum" && parser.is_identifier_at(1) => {
+                parser.pos += 1;
+                file.declarations.push(parser.enum_declaration(doc.take())?);
+            }
+            TokenKind::Identifier(word)
+                if word == "const"
+                    && parser.is_ident_at(1, "enum")
+                    && parser.is_identifier_at(2) =>
+            {
+                parser.pos += 2;
+                file.declarations.push(parser.enum_declaration(doc.take())?);
+            }
+            TokenKind::Punct(p) if matches!(p.as_str(), "{" | "(" | "[") => {
+                doc = None;
+                parser.skip_balanced()?;
+            }
+            _ => {
+                doc = None;
+                parser.pos += 1;
+            }
+        }
+    }
+    Ok(file)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    Identifier(String),
+    /// Single characters, except `=>` and `...`.
+    Punct(String),
+    /// String literal contents without the quotes.
+    String(String),
+    Template,
+    Number(String),
+    /// Text of a `/** ... */` comment, without the stars.
+    DocComment(String),
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based line the token starts on.
+    line: usize,
+}
+
+/// Splits TypeScript source into tokens, dropping whitespace and regular comments.
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    let starts_with = |pos: usize, s: &str| {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| chars.get(pos + i) == Some(&c))
+    };
+
+    while let Some(&c) = chars.get(pos) {
+        let start_line = line;
+        if c == '\n' {
+            line += 1;
+            pos += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let kind = if starts_with(pos, "//") {
+            while chars.get(pos).is_some_and(|c| *c != '\n') {
+                pos += 1;
+            }
+            continue;
+        } else if starts_with(pos, "/*") {
+            let is_doc = starts_with(pos, "/**") && !starts_with(pos, "/**/");
+            let start = pos + if is_doc { 3 } else { 2 };
+            pos = start;
+            while !starts_with(pos, "*/") {
+                match chars.get(pos) {
+                    Some('\n') => line += 1,
+                    Some(_) => {}
+                    None => {
+                        return Err(format!(
+                            "unterminated block comment starting on line {start_line}"
+                        ))
+                    }
+                }
+                pos += 1;
+            }
+            let text: String = chars[start..pos].iter().collect();
+            pos += 2;
+            match is_doc {
+                true => TokenKind::DocComment(doc_text(&text)),
+                false => continue,
+            }
+        } else if c == '"' || c == '\'' {
+            let mut value = String::new();
+            pos += 1;
+            loop {
+                match chars.get(pos) {
+                    Some('\\') => {
+                        value.extend(chars.get(pos + 1));
+                        pos += 2;
+                    }
+                    Some(quote) if *quote == c => break,
+                    Some('\n') | None => {
+                        return Err(format!("unterminated string on line {start_line}"))
+                    }
+                    Some(other) => {
+                        value.push(*other);
+                        pos += 1;
+                    }
+                }
+            }
+            pos += 1;
+            TokenKind::String(value)
+        } else if c == '`' {
+            pos += 1;
+            loop {
+                match chars.get(pos) {
+                    Some('\\') => pos += 1,
+                    Some('`') => break,
+                    Some('\n') => line += 1,
+                    Some(_) => {}
+                    None => {
+                        return Err(format!(
+                            "unterminated template literal on line {start_line}"
+                        ))
+                    }
+                }
+                pos += 1;
+            }
+            pos += 1;
+            TokenKind::Template
+        } else if c.is_ascii_digit() {
+            let start = pos;
+            while chars
+                .get(pos)
+                .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_'))
+            {
+                pos += 1;
+            }
+            TokenKind::Number(chars[start..pos].iter().collect())
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = pos;
+            while chars
+                .get(pos)
+                .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '$'))
+            {
+                pos += 1;
+            }
+            TokenKind::Identifier(chars[start..pos].iter().collect())
+        } else {
+            let punct = ["=>", "..."]
+                .into_iter()
+                .find(|p| starts_with(pos, p))
+                .map(str::to_string)
+                .unwrap_or_else(|| c.to_string());
+            pos += punct.chars().count();
+            TokenKind::Punct(punct)
+        };
+        tokens.push(Token {
+            kind,
+            line: start_line,
+        });
+    }
+    Ok(tokens)
+}
+
+/// The text of a JSDoc comment: leading `*`s removed, surrounding blank lines dropped.
+fn doc_text(comment: &str) -> String {
+    let lines: Vec<&str> = comment
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix('*').map_or(line, str::trim)
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    /// `Name<T> extends Base<T>, Other { ... }`, after the `interface` keyword.
+    fn interface(&mut self, doc: Option<String>) -> Result<TsDeclaration, String> {
+        let name = self.ident()?;
+        let type_params = self.type_params()?;
+        let mut extends = Vec::new();
+        if self.eat_ident("extends") {
+            loop {
+                extends.push(self.postfix_type()?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_punct("{")?;
+        let (members, _) = self.members()?;
+        Ok(TsDeclaration {
+            name,
+            type_params,
+            doc,
+            kind: TsDeclarationKind::Interface { extends, members },
+        })
+    }
+
+    /// `Name<T> = Type;`, after the `type` keyword.
+    fn alias(&mut self, doc: Option<String>) -> Result<TsDeclaration, String> {
+        let name = self.ident()?;
+        let type_params = self.type_params()?;
+        self.expect_punct("=")?;
+        let ty = self.ty()?;
+        self.eat_punct(";");
+        Ok(TsDeclaration {
+            name,
+            type_params,
+            doc,
+            kind: TsDeclarationKind::Alias(ty),
+        })
+    }
+
+    /// `Name { A, B = 'b' }`, after the `enum` keyword.
+    fn enum_declaration(&mut self, doc: Option<String>) -> Result<TsDeclaration, String> {
+        let name = self.ident()?;
+        self.expect_punct("{")?;
+        let mut members = Vec::new();
+        let mut member_doc = None;
+        loop {
+            let token = self.bump()?;
+            let member_name = match token.kind {
+                TokenKind::Punct(p) if p == "}" => break,
+                TokenKind::Punct(p) if p == "," => continue,
+                TokenKind::DocComment(text) => {
+                    member_doc = Some(text);
+                    continue;
+                }
+                TokenKind::Identifier(name) | TokenKind::String(name) => name,
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("expected enum member"));
+                }
+            };
+            let value = match self.eat_punct("=") {
+                true => Some(self.expression_until(&[",", "}"])?),
+                false => None,
+            };
+            members.push(TsEnumMember {
+                name: member_name,
+                value,
+                doc: member_doc.take(),
+            });
+        }
+        Ok(TsDeclaration {
+            name,
+            type_params: Vec::new(),
+            doc,
+            kind: TsDeclarationKind::Enum(members),
+        })
+    }
+
+    /// `<T, U extends X = Y>` on a declaration; constraints and defaults are dropped.
+    fn type_params(&mut self) -> Result<Vec<String>, String> {
+        let mut params = Vec::new();
+        if !self.eat_punct("<") {
+            return Ok(params);
+        }
+        loop {
+            params.push(self.ident()?);
+            if self.eat_ident("extends") {
+                self.ty()?;
+            }
+            if self.eat_punct("=") {
+                self.ty()?;
+            }
+            if self.eat_punct(">") {
+                return Ok(params);
+            }
+            self.expect_punct(",")?;
+        }
+    }
+
+    /// Members of an object type up to and including its closing `}`, and its index
+    /// signature if any. Methods are skipped.
+    fn members(&mut self) -> Result<(Vec<TsMember>, Option<TsIndexSignature>), String> {
+        let mut members = Vec::new();
+        let mut index = None;
+        let mut doc = None;
+        loop {
+            let token = self.bump()?;
+            let name = match token.kind {
+                TokenKind::Punct(p) if p == "}" => return Ok((members, index)),
+                TokenKind::Punct(p) if p == ";" || p == "," => continue,
+                TokenKind::DocComment(text) => {
+                    doc = Some(text);
+                    continue;
+                }
+                TokenKind::Identifier(word)
+                    if word == "readonly" && !self.is_punct(":") && !self.is_punct("?") =>
+                {
+                    continue;
+                }
+                TokenKind::Punct(p) if p == "[" => {
+                    // `[key: string]: T`; computed keys are skipped
+                    if self.is_identifier() && self.is_punct_at(1, ":") {
+                        self.pos += 2;
+                        let key = self.ty()?;
+                        self.expect_punct("]")?;
+                        self.expect_punct(":")?;
+                        index = Some((Box::new(key), Box::new(self.ty()?)));
+                    } else {
+                        self.pos -= 1;
+                        self.skip_balanced()?;
+                        self.skip_member()?;
+                    }
+                    doc = None;
+                    continue;
+                }
+                TokenKind::Identifier(name) | TokenKind::String(name) | TokenKind::Number(name) => {
+                    name
+                }
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("expected property name"));
+                }
+            };
+            let optional = self.eat_punct("?");
+            if self.is_punct("(") || self.is_punct("<") {
+                self.skip_member()?;
+                doc = None;
+                continue;
+            }
+            let ty = match self.eat_punct(":") {
+                true => self.ty()?,
+                false => TsType::Named {
+                    name: "any".to_string(),
+                    args: Vec::new(),
+                },
+            };
+            members.push(TsMember {
+                name,
+                optional,
+                ty,
+                doc: doc.take(),
+            });
+        }
+    }
+
+    /// Skips the rest of a method signature, up to its `;`, `,` or the closing `}`.
+    fn skip_member(&mut self) -> Result<(), String> {
+        loop {
+            if self.is_punct("}") {
+                return Ok(());
+            }
+            if self.eat_punct(";") || self.eat_punct(",") {
+                return Ok(());
+            }
+            match self.is_punct("(") || self.is_punct("{") || self.is_punct("[") {
+                true => self.skip_balanced()?,
+                false => {
+                    if self.is_punct("<") {
+                        // generic parameters and arguments hold no separators of interest
+                        self.pos += 1;
+                        continue;
+                    }
+                    let at_next_line = self.pos > 0
+                        && self
+                            .peek()
+                            .is_some_and(|t| t.line > self.tokens[self.pos - 1].line)
+                        && self.is_identifier()
+                        && (self.is_punct_at(1, ":") || self.is_punct_at(1, "?"));
+                    if at_next_line {
+                        return Ok(());
+                    }
+                    self.bump()?;
+                }
+            }
+        }
+    }
+
+    /// A union, intersection or single type.
+    fn ty(&mut self) -> Result<TsType, String> {
+        self.eat_punct("|");
+        let mut items = vec![self.intersection()?];
+        while self.eat_punct("|") {
+            items.push(self.intersection()?);
+        }
+        Ok(match items.len() {
+            1 => items.remove(0),
+            _ => TsType::Union(items),
+        })
+    }
+
+    fn intersection(&mut self) -> Result<TsType, String> {
+        self.eat_punct("&");
+        let mut items = vec![self.postfix_type()?];
+        while self.eat_punct("&") {
+            items.push(self.postfix_type()?);
+        }
+        Ok(match items.len() {
+            1 => items.remove(0),
+            _ => TsType::Intersection(items),
+        })
+    }
+
+    /// A primary type followed by any number of `[]`.
+    fn postfix_type(&mut self) -> Result<TsType, String> {
+        let mut ty = self.primary_type()?;
+        while self.is_punct("[") && self.is_punct_at(1, "]") {
+            self.pos += 2;
+            ty = TsType::Array(Box::new(ty));
+        }
+        Ok(ty)
+    }
+
+    fn primary_type(&mut self) -> Result<TsType, String> {
+        let token = self.bump()?;
+        match token.kind {
+            TokenKind::Punct(p) if p == "(" => {
+                self.pos -= 1;
+                let start = self.pos;
+                self.skip_balanced()?;
+                if self.eat_punct("=>") {
+                    // function types have no DTO counterpart; keep the name for the mapping
+                    self.ty()?;
+                    return Ok(named("Function", Vec::new()));
+                }
+                self.pos = start + 1;
+                let ty = self.ty()?;
+                self.expect_punct(")")?;
+                Ok(ty)
+            }
+            TokenKind::Punct(p) if p == "{" => {
+                let (members, index) = self.members()?;
+                Ok(TsType::Object { members, index })
+            }
+            TokenKind::Punct(p) if p == "[" => {
+                let mut items = Vec::new();
+                while !self.eat_punct("]") {
+                    self.eat_punct("...");
+                    // labeled elements: `[name: string, age?: number]`
+                    if self.is_identifier()
+                        && (self.is_punct_at(1, ":") || self.is_punct_at(1, "?"))
+                    {
+                        self.pos += 1;
+                        self.eat_punct("?");
+                        self.expect_punct(":")?;
+                    }
+                    items.push(self.ty()?);
+                    self.eat_punct("?");
+                    if !self.eat_punct(",") {
+                        self.expect_punct("]")?;
+                        break;
+                    }
+                }
+                Ok(TsType::Tuple(items))
+            }
+            TokenKind::Punct(p) if p == "-" => match self.bump()?.kind {
+                TokenKind::Number(number) => Ok(TsType::Literal(format!("-{number}"))),
+                _ => {
+                    self.pos -= 1;
+                    Err(self.error("expected number"))
+                }
+            },
+            TokenKind::String(value) => Ok(TsType::StringLiteral(value)),
+            TokenKind::Number(number) => Ok(TsType::Literal(number)),
+            TokenKind::Template => Ok(named("string", Vec::new())),
+            TokenKind::Identifier(word) if word == "true" || word == "false" => {
+                Ok(TsType::Literal(word))
+            }
+            TokenKind::Identifier(word)
+                if matches!(word.as_str(), "keyof" | "typeof" | "infer") =>
+            {
+                self.pos -= 1;
+                Err(self.error("unsupported type operator"))
+            }
+            TokenKind::Identifier(word) if word == "readonly" => self.postfix_type(),
+            TokenKind::Identifier(mut name) => {
+                while self.is_punct(".") && self.is_identifier_at(1) {
+                    self.pos += 1;
+                    name = format!("{name}.{}", self.ident()?);
+                }
+                let mut args = Vec::new();
+                if self.eat_punct("<") {
+                    loop {
+                        args.push(self.ty()?);
+                        if self.eat_punct(">") {
+                            break;
+                        }
+                        self.expect_punct(",")?;
+                    }
+                }
+                Ok(match (name.as_str(), args.len()) {
+                    ("Array" | "ReadonlyArray", 1) => TsType::Array(Box::new(args.remove(0))),
+                    _ => named(&name, args),
+                })
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected type"))
+            }
+        }
+    }
+
+    /// The source of an expression up to, not including, one of `terminators` at depth 0.
+    fn expression_until(&mut self, terminators: &[&str]) -> Result<String, String> {
+        let mut parts: Vec<String> = Vec::new();
+        while !terminators.iter().any(|t| self.is_punct(t)) {
+            let token = self.bump()?;
+            let part = match token.kind {
+                TokenKind::Identifier(text) | TokenKind::Number(text) | TokenKind::Punct(text) => {
+                    text
+                }
+                TokenKind::String(value) => format!("'{value}'"),
+                TokenKind::Template | TokenKind::DocComment(_) => continue,
+            };
+            // shifts are lexed one `<` or `>` at a time, and `1 < < 0` isn't an expression
+            match parts.last_mut() {
+                Some(last)
+                    if matches!(part.as_str(), "<" | ">")
+                        && last.chars().all(|c| part.starts_with(c)) =>
+                {
+                    last.push_str(&part)
+                }
+                _ => parts.push(part),
+            }
+        }
+        Ok(parts.join(" "))
+    }
+
+    /// Skips a bracketed group starting at the current `(`, `{` or `[`.
+    fn skip_balanced(&mut self) -> Result<(), String> {
+        let mut depth = 0;
+        loop {
+            match self.bump()?.kind {
+                TokenKind::Punct(p) if matches!(p.as_str(), "(" | "{" | "[") => depth += 1,
+                TokenKind::Punct(p) if matches!(p.as_str(), ")" | "}" | "]") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of file".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Identifier(word)) => {
+                let word = word.clone();
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    fn is_identifier(&self) -> bool {
+        self.is_identifier_at(0)
+    }
+
+    fn is_identifier_at(&self, offset: usize) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Identifier(_))
+        )
+    }
+
+    fn is_ident_at(&self, offset: usize, word: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Identifier(w)) if w == word
+        )
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        self.is_punct_at(0, punct)
+    }
+
+    fn is_punct_at(&self, offset: usize, punct: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos + offset).map(|t| &t.kind),
+            Some(TokenKind::Punct(p)) if p == punct
+        )
+    }
+
+    fn eat_ident(&mut self, word: &str) -> bool {
+        if self.is_ident_at(0, word) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if self.is_punct(punct) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), String> {
+        match self.eat_punct(punct) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{punct}`"))),
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        match self.peek() {
+            Some(token) => format!("{message} on line {}, found {:?}", token.line, token.kind),
+            None => format!("{message}, found end of file"),
+        }
+    }
+}
+
+fn named(name: &str, args: Vec<TsType>) -> TsType {
+    TsType::Named {
+        name: name.to_string(),
+        args,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(source: &str) -> Vec<TsMember> {
+        match &parse_typescript(source).unwrap().declarations[0].kind {
+            TsDeclarationKind::Interface { members, .. } => members.clone(),
+            other => panic!("expected an interface, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_interfaces_aliases_and_enums() {
+        let file = parse_typescript(
+            r#"
+            import { Money } from './money';
+
+            /** A customer. */
+            export interface CustomerDto<T> extends EntityDto, Audited<T> {
+                name: string;
+            }
+            export type Status = 'active' | 'blocked';
+            export const enum Color { Red, Green = 'green' }
+            export function helper(): void { const type = 1; interface Local {} }
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = file.declarations.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["CustomerDto", "Status", "Color"]);
+
+        let customer = &file.declarations[0];
+        assert_eq!(customer.doc.as_deref(), Some("A customer."));
+        assert_eq!(customer.type_params, vec!["T"]);
+        let TsDeclarationKind::Interface { extends, .. } = &customer.kind else {
+            panic!("expected an interface");
+        };
+        assert_eq!(
+            extends,
+            &vec![
+                named("EntityDto", vec![]),
+                named("Audited", vec![named("T", vec![])])
+            ]
+        );
+
+        let TsDeclarationKind::Enum(colors) = &file.declarations[2].kind else {
+            panic!("expected an enum");
+        };
+        assert_eq!(colors[1].value.as_deref(), Some("'green'"));
+    }
+
+    #[test]
+    fn test_member_types() {
+        let members = members(
+            r#"
+            interface Dto {
+                readonly id: number,
+                /**
+                 * The display name.
+                 * @deprecated use title
+                 */
+                name?: string | null
+                tags: Array<string>; scores: number[][]
+                'first-name': string;
+                pair: [label: string, value?: number];
+                nested: { a: boolean; [key: string]: unknown };
+                kind: | 'a' | 'b';
+                onChange(value: string): void;
+                callback: (value: string) => void;
+                extra
+            }
+            "#,
+        );
+        let summary: Vec<(&str, bool, &TsType)> = members
+            .iter()
+            .map(|m| (m.name.as_str(), m.optional, &m.ty))
+            .collect();
+        let string = || named("string", vec![]);
+        let number = || named("number", vec![]);
+        assert_eq!(
+            summary,
+            vec![
+                ("id", false, &number()),
+                (
+                    "name",
+                    true,
+                    &TsType::Union(vec![string(), named("null", vec![])])
+                ),
+                ("tags", false, &TsType::Array(Box::new(string()))),
+                (
+                    "scores",
+                    false,
+                    &TsType::Array(Box::new(TsType::Array(Box::new(number()))))
+                ),
+                ("first-name", false, &string()),
+                ("pair", false, &TsType::Tuple(vec![string(), number()])),
+                (
+                    "nested",
+                    false,
+                    &TsType::Object {
+                        members: vec![TsMember {
+                            name: "a".to_string(),
+                            optional: false,
+                            ty: named("boolean", vec![]),
+                            doc: None,
+                        }],
+                        index: Some((Box::new(string()), Box::new(named("unknown", vec![])))),
+                    }
+                ),
+                (
+                    "kind",
+                    false,
+                    &TsType::Union(vec![
+                        TsType::StringLiteral("a".to_string()),
+                        TsType::StringLiteral("b".to_string())
+                    ])
+                ),
+                ("callback", false, &named("Function", vec![])),
+                ("extra", false, &named("any", vec![])),
+            ]
+        );
+        assert_eq!(
+            members[1].doc.as_deref(),
+            Some("The display name.\n@deprecated use title")
+        );
+    }
+
+    #[test]
+    fn test_strip_null() {
+        let ty = TsType::Union(vec![
+            named("string", vec![]),
+            named("undefined", vec![]),
+            named("null", vec![]),
+        ]);
+        assert_eq!(ty.strip_null(), (named("string", vec![]), true));
+    }
+
+    #[test]
+    fn test_unsupported_types_are_errors() {
+        let error = parse_typescript("type Keys = keyof Dto;").unwrap_err();
+        assert!(
+            error.contains("unsupported type operator on line 1"),
+            "{error}"
+        );
+    }
+}
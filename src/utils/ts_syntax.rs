@@ -0,0 +1,97 @@
+/// Syntax model of the type declarations in a TypeScript file. Everything else
+/// (functions, classes, values, imports) is skipped by the parser.
+#[derive(Clone, Debug, Default)]
+pub struct TsFile {
+    pub declarations: Vec<TsDeclaration>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TsDeclaration {
+    pub name: String,
+    pub type_params: Vec<String>,
+    /// Text of the `/** ... */` comment before the declaration, without the stars.
+    pub doc: Option<String>,
+    pub kind: TsDeclarationKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum TsDeclarationKind {
+    /// `interface Name extends Base { ... }`
+    Interface {
+        extends: Vec<TsType>,
+        members: Vec<TsMember>,
+    },
+    /// `type Name = ...`
+    Alias(TsType),
+    /// `enum Name { A, B = 'b' }`
+    Enum(Vec<TsEnumMember>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsMember {
+    pub name: String,
+    /// Declared with `?`.
+    pub optional: bool,
+    pub ty: TsType,
+    pub doc: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TsEnumMember {
+    pub name: String,
+    /// Initializer as written, e.g. `'active'` or `1 << 2`.
+    pub value: Option<String>,
+    pub doc: Option<String>,
+}
+
+/// The key and value types of an index signature `[key: string]: T`.
+pub type TsIndexSignature = (Box<TsType>, Box<TsType>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TsType {
+    /// A possibly generic type name: `string`, `Date`, `Page<T>`, `Record<string, number>`.
+    Named {
+        name: String,
+        args: Vec<TsType>,
+    },
+    /// `T[]`, `Array<T>` and `ReadonlyArray<T>`.
+    Array(Box<TsType>),
+    Union(Vec<TsType>),
+    Intersection(Vec<TsType>),
+    Tuple(Vec<TsType>),
+    /// An inline object type `{ ... }`. An index signature `[key: string]: T` is kept
+    /// as its key and value types.
+    Object {
+        members: Vec<TsMember>,
+        index: Option<TsIndexSignature>,
+    },
+    /// A string literal type, unquoted.
+    StringLiteral(String),
+    /// A number, `true`/`false` literal type as written.
+    Literal(String),
+}
+
+impl TsType {
+    /// The name of a type without generic arguments, e.g. `null` or `string`.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            TsType::Named { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Splits `null` and `undefined` out of a union, reporting whether either was present.
+    pub fn strip_null(&self) -> (TsType, bool) {
+        let TsType::Union(items) = self else {
+            return (self.clone(), false);
+        };
+        let (nulls, rest): (Vec<&TsType>, Vec<&TsType>) = items
+            .iter()
+            .partition(|item| matches!(item.name(), Some("null" | "undefined")));
+        let ty = match rest.as_slice() {
+            [single] => (*single).clone(),
+            _ => TsType::Union(rest.into_iter().cloned().collect()),
+        };
+        (ty, !nulls.is_empty())
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// C# type names to the TypeScript types they are emitted as.
+const BUILTIN_TYPES: [(&str, &str); 40] = [
+    ("bool", "boolean"),
+    ("Boolean", "boolean"),
+    ("byte", "number"),
+    ("Byte", "number"),
+    ("sbyte", "number"),
+    ("SByte", "number"),
+    ("short", "number"),
+    ("Int16", "number"),
+    ("ushort", "number"),
+    ("UInt16", "number"),
+    ("int", "number"),
+    ("Int32", "number"),
+    ("uint", "number"),
+    ("UInt32", "number"),
+    ("long", "number"),
+    ("Int64", "number"),
+    ("ulong", "number"),
+    ("UInt64", "number"),
+    ("float", "number"),
+    ("Single", "number"),
+    ("double", "number"),
+    ("Double", "number"),
+    ("decimal", "number"),
+    ("Decimal", "number"),
+    ("string", "string"),
+    ("String", "string"),
+    ("char", "string"),
+    ("Char", "string"),
+    ("Guid", "string"),
+    ("DateTime", "Date"),
+    ("DateTimeOffset", "Date"),
+    ("DateOnly", "string"),
+    ("TimeOnly", "string"),
+    ("TimeSpan", "string"),
+    ("Uri", "string"),
+    ("object", "unknown"),
+    ("Object", "unknown"),
+    ("dynamic", "any"),
+    ("JsonElement", "unknown"),
+    ("JsonNode", "unknown"),
+];
+
+/// Shape of a type-map YAML file:
+///
+/// ```yaml
+/// types:
+///   DateTime: string
+///   Guid: UUID
+///   long: bigint
+/// imports:
+///   - "import type { UUID } from './uuid';"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TypeMapFile {
+    types: HashMap<String, String>,
+    imports: Vec<String>,
+}
+
+/// Mapping from C# type names to TypeScript types: the built-in defaults,
+/// optionally overridden by a YAML file.
+#[derive(Clone, Debug)]
+pub struct TsTypeMap {
+    types: HashMap<String, String>,
+    /// Lines written at the top of the output, for types the map refers to.
+    pub imports: Vec<String>,
+}
+
+impl Default for TsTypeMap {
+    fn default() -> Self {
+        Self {
+            types: BUILTIN_TYPES
+                .iter()
+                .map(|(cs, ts)| (cs.to_string(), ts.to_string()))
+                .collect(),
+            imports: Vec::new(),
+        }
+    }
+}
+
+impl TsTypeMap {
+    /// Parses a type-map file and merges it over the built-in defaults.
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let file: TypeMapFile =
+            serde_yaml::from_str(yaml).map_err(|e| format!("invalid type map: {e}"))?;
+        let mut type_map = Self::default();
+        type_map.types.extend(file.types);
+        type_map.imports = file.imports;
+        Ok(type_map)
+    }
+
+    /// Looks up a type by its name as written, then by its unqualified name,
+    /// so `System.Guid` can be mapped separately from `Guid` if needed.
+    pub fn get(&self, csharp_name: &str) -> Option<&str> {
+        let simple_name = csharp_name.rsplit(['.', ':']).next().unwrap_or(csharp_name);
+        self.types
+            .get(csharp_name)
+            .or_else(|| self.types.get(simple_name))
+            .map(|ts| ts.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_defaults() {
+        let type_map = TsTypeMap::default();
+        assert_eq!(type_map.get("int"), Some("number"));
+        assert_eq!(type_map.get("Guid"), Some("string"));
+        assert_eq!(type_map.get("System.Guid"), Some("string"));
+        assert_eq!(type_map.get("DateOnly"), Some("string"));
+        assert_eq!(type_map.get("object"), Some("unknown"));
+        assert_eq!(type_map.get("CustomerDto"), None);
+    }
+
+    #[test]
+    fn test_yaml_overrides_defaults() {
+        let type_map = TsTypeMap::from_yaml(
+            r#"
+types:
+  DateTime: string
+  Guid: UUID
+  long: bigint
+  Money: string
+imports:
+  - "import type { UUID } from './uuid';"
+"#,
+        )
+        .unwrap();
+        assert_eq!(type_map.get("DateTime"), Some("string"));
+        assert_eq!(type_map.get("Guid"), Some("UUID"));
+        assert_eq!(type_map.get("long"), Some("bigint"));
+        assert_eq!(type_map.get("Acme.Money"), Some("string"));
+        assert_eq!(type_map.get("int"), Some("number"));
+        assert_eq!(
+            type_map.imports,
+            vec!["import type { UUID } from './uuid';"]
+        );
+    }
+
+    #[test]
+    fn test_empty_yaml_keeps_defaults() {
+        let type_map = TsTypeMap::from_yaml("{}").unwrap();
+        assert_eq!(type_map.get("bool"), Some("boolean"));
+        let type_map = TsTypeMap::from_yaml("").unwrap();
+        assert_eq!(type_map.get("bool"), Some("boolean"));
+    }
+
+    #[test]
+    fn test_invalid_yaml_is_an_error() {
+        assert!(TsTypeMap::from_yaml("types: [not, a, map]").is_err());
+        assert!(TsTypeMap::from_yaml("typos:\n  int: number").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+/// Declared types keyed by their path, `OrderDto.Item` for an `Item` nested in `OrderDto`,
+/// and looked up the way C# binds a type name: among the nested types of the declaration
+/// using it, then of each type enclosing that one, then at the top level.
+pub struct TypeScopes<T> {
+    types: HashMap<String, T>,
+}
+
+impl<T> Default for TypeScopes<T> {
+    fn default() -> Self {
+        Self {
+            types: HashMap::new(),
+        }
+    }
+}
+
+impl<T> TypeScopes<T> {
+    /// Adds a type unless one with the same path is known, so the first file wins.
+    pub fn insert(&mut self, path: String, value: T) {
+        self.types.entry(path).or_insert(value);
+    }
+
+    /// The type `name` refers to inside the declaration at path `scope` (`""` outside
+    /// any type). A qualified name such as `OrderDto.Item` is also tried without its
+    /// leading namespaces.
+    pub fn resolve(&self, name: &str, scope: &str) -> Option<&T> {
+        let name = name.rsplit("::").next().unwrap_or(name);
+        let segments: Vec<&str> = name.split('.').collect();
+        (0..segments.len()).find_map(|skip| {
+            let name = segments[skip..].join(".");
+            enclosing_scopes(scope).find_map(|scope| self.types.get(&type_path(scope, &name)))
+        })
+    }
+}
+
+/// The path of type `name` declared in the type at path `parent`.
+pub fn type_path(parent: &str, name: &str) -> String {
+    match parent {
+        "" => name.to_string(),
+        parent => format!("{parent}.{name}"),
+    }
+}
+
+/// `scope` and every type enclosing it, innermost first, then the top level.
+fn enclosing_scopes(scope: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(scope), |scope| match *scope {
+        "" => None,
+        scope => Some(scope.rsplit_once('.').map_or("", |(outer, _)| outer)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_types_shadow_outer_ones() {
+        let mut scopes = TypeScopes::default();
+        for path in ["Item", "Request.Item", "Response.Item", "Response"] {
+            scopes.insert(path.to_string(), path);
+        }
+        scopes.insert("Item".to_string(), "shadowed");
+        assert_eq!(scopes.resolve("Item", "Response"), Some(&"Response.Item"));
+        assert_eq!(
+            scopes.resolve("Item", "Response.Item"),
+            Some(&"Response.Item")
+        );
+        assert_eq!(scopes.resolve("Item", "Other"), Some(&"Item"));
+        assert_eq!(scopes.resolve("Item", ""), Some(&"Item"));
+        assert_eq!(
+            scopes.resolve("global::Dtos.Request.Item", "Response"),
+            Some(&"Request.Item")
+        );
+        assert_eq!(scopes.resolve("Missing", "Response"), None);
+    }
+}
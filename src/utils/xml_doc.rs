@@ -0,0 +1,301 @@
+/// The parts of a C# XML documentation comment that generated code carries over,
+/// rendered as Markdown-flavoured plain text.
+#[derive(Debug, Default, PartialEq)]
+pub struct XmlDoc {
+    pub summary: String,
+    pub remarks: String,
+    pub example: String,
+    /// `<param name="...">` descriptions, in declaration order.
+    pub params: Vec<(String, String)>,
+}
+
+impl XmlDoc {
+    /// Parses the lines of a `///` comment. Text outside any tag counts as the summary,
+    /// and malformed markup degrades to its text rather than failing.
+    pub fn parse(lines: &[String]) -> Self {
+        let nodes = Reader {
+            chars: lines.join("\n").chars().collect(),
+            pos: 0,
+            open: Vec::new(),
+        }
+        .nodes();
+
+        let mut doc = XmlDoc::default();
+        let mut loose_text: Vec<Node> = Vec::new();
+        for node in nodes {
+            match node {
+                Node::Element {
+                    name,
+                    attrs,
+                    children,
+                } => match name.as_str() {
+                    "summary" => doc.summary = render(&children),
+                    "remarks" => doc.remarks = render(&children),
+                    "example" => doc.example = render(&children),
+                    "param" => {
+                        let param = attribute(&attrs, "name").unwrap_or_default();
+                        doc.params.push((param.to_string(), render(&children)));
+                    }
+                    _ => {}
+                },
+                text => loose_text.push(text),
+            }
+        }
+        if doc.summary.is_empty() {
+            doc.summary = render(&loose_text);
+        }
+        doc
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+}
+
+struct Reader {
+    chars: Vec<char>,
+    pos: usize,
+    /// Names of the elements being read, innermost last.
+    open: Vec<String>,
+}
+
+impl Reader {
+    /// Reads nodes until the closing tag of the innermost open element, or the end of input.
+    fn nodes(&mut self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+        while let Some(&c) = self.chars.get(self.pos) {
+            if c != '<' {
+                text.push(c);
+                self.pos += 1;
+                continue;
+            }
+            if !text.is_empty() {
+                nodes.push(Node::Text(decode_entities(&std::mem::take(&mut text))));
+            }
+            let start = self.pos;
+            let tag = self.until('>');
+            if let Some(closing) = tag.strip_prefix('/') {
+                let closing = closing.trim();
+                if self.open.last().is_some_and(|name| name == closing) {
+                    return nodes;
+                }
+                // closing an outer element also closes this one; anything else is dropped
+                if self.open.iter().any(|name| name == closing) {
+                    self.pos = start;
+                    return nodes;
+                }
+                continue;
+            }
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name: String = tag.chars().take_while(|c| !c.is_whitespace()).collect();
+            let attrs = attributes(&tag[name.len()..]);
+            let children = match self_closing {
+                true => Vec::new(),
+                false => {
+                    self.open.push(name.clone());
+                    let children = self.nodes();
+                    self.open.pop();
+                    children
+                }
+            };
+            nodes.push(Node::Element {
+                name,
+                attrs,
+                children,
+            });
+        }
+        if !text.is_empty() {
+            nodes.push(Node::Text(decode_entities(&text)));
+        }
+        nodes
+    }
+
+    /// The text after the current `<` up to `end`, consuming both.
+    fn until(&mut self, end: char) -> String {
+        self.pos += 1;
+        let mut tag = String::new();
+        while let Some(&c) = self.chars.get(self.pos) {
+            self.pos += 1;
+            if c == end {
+                break;
+            }
+            tag.push(c);
+        }
+        tag
+    }
+}
+
+/// `name="value"` pairs of a start tag.
+fn attributes(source: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let mut rest = source;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(len) = after[1..].find(quote) else {
+            break;
+        };
+        attrs.push((name, decode_entities(&after[1..1 + len])));
+        rest = &after[len + 2..];
+    }
+    attrs
+}
+
+fn attribute<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(attr, _)| attr == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Renders markup as text: code becomes backticks, references become `{@link ...}`
+/// and paragraphs are separated by blank lines.
+fn render(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element {
+                name,
+                attrs,
+                children,
+            } => {
+                let inner = render(children);
+                match name.as_str() {
+                    "c" => out.push_str(&format!("`{inner}`")),
+                    "code" => out.push_str(&format!("\n```\n{inner}\n```\n")),
+                    "para" => out.push_str(&format!("\n\n{inner}\n\n")),
+                    "br" => out.push('\n'),
+                    "item" => out.push_str(&format!("\n- {}", inner.trim())),
+                    "paramref" | "typeparamref" => {
+                        let param = attribute(attrs, "name").unwrap_or_default();
+                        out.push_str(&format!("`{param}`"));
+                    }
+                    "see" | "seealso" => {
+                        if let Some(cref) = attribute(attrs, "cref") {
+                            out.push_str(&format!("{{@link {}}}", cref_name(cref)));
+                        } else if let Some(href) = attribute(attrs, "href") {
+                            match inner.trim().is_empty() {
+                                true => out.push_str(&format!("{{@link {href}}}")),
+                                false => {
+                                    out.push_str(&format!("{{@link {href} {}}}", inner.trim()))
+                                }
+                            }
+                        } else if let Some(keyword) = attribute(attrs, "langword") {
+                            out.push_str(&format!("`{keyword}`"));
+                        } else {
+                            out.push_str(&inner);
+                        }
+                    }
+                    _ => out.push_str(&inner),
+                }
+            }
+        }
+    }
+    normalize(&out)
+}
+
+/// `T:Acme.Dtos.CustomerDto` → `CustomerDto`, `List{T}` → `List`.
+fn cref_name(cref: &str) -> &str {
+    let cref = cref.split_once(':').map_or(cref, |(_, name)| name);
+    let cref = cref.split(['{', '(', '`']).next().unwrap_or(cref);
+    cref.rsplit('.').next().unwrap_or(cref)
+}
+
+/// Trims every line and collapses runs of blank lines.
+fn normalize(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(doc: &str) -> XmlDoc {
+        let lines: Vec<String> = doc.lines().map(|l| l.trim().to_string()).collect();
+        XmlDoc::parse(&lines)
+    }
+
+    #[test]
+    fn test_summary_remarks_and_params() {
+        let doc = parse(
+            r#"
+            <summary>
+            The customer's <c>Id</c> &amp; name.
+            </summary>
+            <remarks>
+            See <see cref="T:Acme.Dtos.OrderDto"/> and <see href="https://example.com">the docs</see>.
+            <para>Never <see langword="null"/>.</para>
+            </remarks>
+            <param name="Id">Primary key.</param>
+            "#,
+        );
+        assert_eq!(doc.summary, "The customer's `Id` & name.");
+        assert_eq!(
+            doc.remarks,
+            "See {@link OrderDto} and {@link https://example.com the docs}.\n\nNever `null`."
+        );
+        assert_eq!(doc.param("Id"), Some("Primary key."));
+        assert_eq!(doc.param("Name"), None);
+    }
+
+    #[test]
+    fn test_text_without_tags_is_the_summary() {
+        assert_eq!(parse("Just a note.").summary, "Just a note.");
+    }
+
+    #[test]
+    fn test_malformed_markup_keeps_text() {
+        let doc = parse("<summary>Unclosed <b>bold\n</summary><remarks>Left open");
+        assert_eq!(doc.summary, "Unclosed bold");
+        assert_eq!(doc.remarks, "Left open");
+    }
+
+    #[test]
+    fn test_lists_and_code() {
+        let doc = parse(
+            r#"
+            <summary>Flags:
+            <list type="bullet"><item><description>One</description></item><item>Two</item></list>
+            <code>var x = 1;</code>
+            </summary>
+            "#,
+        );
+        assert_eq!(doc.summary, "Flags:\n- One\n- Two\n\n```\nvar x = 1;\n```");
+    }
+}